@@ -0,0 +1,158 @@
+use crate::preprocess::html_to_text;
+use anyhow::{bail, Result};
+use std::process::Command;
+
+/// Give up on a page that takes longer than this to download
+const FETCH_TIMEOUT_SECS: u32 = 20;
+
+/// Page furniture that is never part of the article
+const BOILERPLATE_TAGS: &[&str] = &["nav", "footer", "aside", "form", "menu"];
+
+/// Paragraphs with at least this many words are prose; shorter ones are headings,
+/// captions or leftover links
+const PROSE_WORDS: usize = 10;
+/// Short paragraphs in a row that end a block of prose
+const MAX_SHORT_RUN: usize = 3;
+
+/// Download `url` and return the text of its main article. Uses the system `curl`,
+/// which ships with macOS, Windows 10 and most Linux systems.
+pub fn fetch(url: &str) -> Result<String> {
+    if !(url.starts_with("http://") || url.starts_with("https://")) {
+        bail!("not a web address: {}", url);
+    }
+    let output = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "--location", "--compressed"])
+        .args(["--max-time", &FETCH_TIMEOUT_SECS.to_string()])
+        .args(["--user-agent", "Mozilla/5.0 (compatible; Speeder)"])
+        .arg(url)
+        .output()
+        .map_err(|e| anyhow::anyhow!("can't run curl: {}", e))?;
+    if !output.status.success() {
+        // "curl: (6) Could not resolve host: example.invalid" reads best without the prefix
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let message = stderr.trim().rsplit(") ").next().unwrap_or("download failed").to_string();
+        bail!("{}", message);
+    }
+
+    let text = extract_article(&String::from_utf8_lossy(&output.stdout));
+    if text.is_empty() {
+        bail!("no article text on the page");
+    }
+    Ok(text)
+}
+
+/// The main text of a web page: the `<article>` (or `<main>`) element when there is
+/// one, navigation and other furniture removed, then the largest block of prose paragraphs
+pub fn extract_article(html: &str) -> String {
+    let lower = html.to_ascii_lowercase();
+    let content = ["article", "main", "body"]
+        .iter()
+        .find_map(|tag| element_contents(html, &lower, tag))
+        .unwrap_or(html);
+    let text = html_to_text(&remove_elements(content, BOILERPLATE_TAGS));
+    largest_prose_block(&text)
+}
+
+/// Contents of the first `<tag>` element, or None if there isn't one
+fn element_contents<'a>(html: &'a str, lower: &str, tag: &str) -> Option<&'a str> {
+    let open = find_tag(lower, &format!("<{}", tag), 0)?;
+    let start = open + lower[open..].find('>')? + 1;
+    let end = lower[start..].rfind(&format!("</{}", tag)).map_or(html.len(), |i| start + i);
+    Some(&html[start..end])
+}
+
+/// Byte offset of the next `<tag` at or after `from` that is the whole tag name
+/// (so `<nav` doesn't match `<navbar>`)
+fn find_tag(lower: &str, open: &str, from: usize) -> Option<usize> {
+    let mut at = from;
+    while let Some(i) = lower[at..].find(open) {
+        let found = at + i;
+        let name_ends = match lower[found + open.len()..].chars().next() {
+            Some(c) => !(c.is_ascii_alphanumeric() || c == '-'),
+            None => true,
+        };
+        if name_ends {
+            return Some(found);
+        }
+        at = found + open.len();
+    }
+    None
+}
+
+/// `html` without the elements named in `tags`, nested ones included
+fn remove_elements(html: &str, tags: &[&str]) -> String {
+    let mut html = html.to_string();
+    for tag in tags {
+        let open = format!("<{}", tag);
+        let close = format!("</{}", tag);
+        let mut from = 0;
+        loop {
+            let lower = html.to_ascii_lowercase();
+            let Some(start) = find_tag(&lower, &open, from) else {
+                break;
+            };
+            // Walk to the matching close tag, counting nested elements of the same name
+            let mut depth = 0;
+            let mut at = start;
+            let end = loop {
+                let next_open = find_tag(&lower, &open, at + 1);
+                let Some(next_close) = find_tag(&lower, &close, at + 1) else {
+                    break html.len();
+                };
+                match next_open {
+                    Some(o) if o < next_close => {
+                        depth += 1;
+                        at = o;
+                    }
+                    _ if depth > 0 => {
+                        depth -= 1;
+                        at = next_close;
+                    }
+                    _ => break lower[next_close..].find('>').map_or(html.len(), |i| next_close + i + 1),
+                }
+            };
+            html.replace_range(start..end, " ");
+            from = start;
+        }
+    }
+    html
+}
+
+/// The run of paragraphs holding the most words, where prose continues across
+/// headings and other short paragraphs but not across several of them in a row
+fn largest_prose_block(text: &str) -> String {
+    let paragraphs: Vec<&str> = text.split("\n\n").map(str::trim).filter(|p| !p.is_empty()).collect();
+    let words = |p: &str| p.split_whitespace().count();
+
+    let mut best = (0, 0, 0); // First and last paragraph of the block, and its words
+    let mut current: Option<(usize, usize, usize)> = None;
+    let mut short_run = 0;
+    for (i, paragraph) in paragraphs.iter().enumerate() {
+        let count = words(paragraph);
+        if count < PROSE_WORDS {
+            short_run += 1;
+            if short_run >= MAX_SHORT_RUN {
+                current = None;
+            }
+            continue;
+        }
+        short_run = 0;
+        let (first, total) = match current {
+            Some((first, _, total)) => (first, total),
+            // A heading right before the first paragraph belongs with it
+            None if i > 0 && words(paragraphs[i - 1]) < PROSE_WORDS => (i - 1, 0),
+            None => (i, 0),
+        };
+        let block = (first, i, total + count);
+        if block.2 > best.2 {
+            best = block;
+        }
+        current = Some(block);
+    }
+
+    if best.2 == 0 {
+        // No prose at all: keep whatever text there is
+        return paragraphs.join("\n\n");
+    }
+    paragraphs[best.0..=best.1].join("\n\n")
+}
@@ -0,0 +1,108 @@
+use crate::config::Config;
+use crate::language::LanguageProfile;
+use crate::preprocess;
+use crate::tokenizer::{mark_headings, merge_spaced_emphasis, tokenize};
+use anyhow::{ensure, Result};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Times for running the text pipeline over the same input `runs` times
+#[derive(Debug, Clone)]
+pub struct TokenizeBench {
+    pub runs: usize,
+    pub words: usize,                         // Words produced by each run
+    pub stages: Vec<(&'static str, Duration)>, // Time per pipeline stage, summed over all runs
+}
+
+impl TokenizeBench {
+    pub fn total(&self) -> Duration {
+        self.stages.iter().map(|(_, time)| *time).sum()
+    }
+
+    pub fn words_per_sec(&self) -> f64 {
+        let secs = self.total().as_secs_f64();
+        if secs == 0.0 {
+            return 0.0;
+        }
+        (self.words * self.runs) as f64 / secs
+    }
+}
+
+/// Run `text` through preprocessing, tokenization and structure detection `runs` times,
+/// the way a read would with `config`. Fails if runs disagree on the word count.
+pub fn tokenize_throughput(text: &str, config: &Config, runs: usize) -> Result<TokenizeBench> {
+    let mut preprocess_time = Duration::ZERO;
+    let mut tokenize_time = Duration::ZERO;
+    let mut structure_time = Duration::ZERO;
+    let mut words = None;
+
+    for _ in 0..runs.max(1) {
+        let start = Instant::now();
+        let prepared = preprocess::apply(text, &config.preprocess);
+        let profile = if config.language.auto_detect {
+            LanguageProfile::detect(&prepared)
+        } else {
+            LanguageProfile::default()
+        };
+        preprocess_time += start.elapsed();
+
+        let start = Instant::now();
+        let mut tokens = tokenize(&prepared, profile.tokenization, config.display.max_word_chars);
+        tokenize_time += start.elapsed();
+
+        let start = Instant::now();
+        if config.timing.detect_spaced_emphasis {
+            tokens = merge_spaced_emphasis(tokens);
+        }
+        if config.timing.detect_headings {
+            mark_headings(&mut tokens);
+        }
+        structure_time += start.elapsed();
+
+        let count = *words.get_or_insert(tokens.len());
+        ensure!(count == tokens.len(), "runs disagree: {} words, then {}", count, tokens.len());
+    }
+
+    Ok(TokenizeBench {
+        runs: runs.max(1),
+        words: words.unwrap_or(0),
+        stages: vec![
+            ("preprocess", preprocess_time),
+            ("tokenize", tokenize_time),
+            ("structure", structure_time),
+        ],
+    })
+}
+
+impl fmt::Display for TokenizeBench {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} runs of {} words in {:.3?}", self.runs, self.words, self.total())?;
+        for (stage, time) in &self.stages {
+            writeln!(f, "  {:<10} {:>10.3?}", stage, time)?;
+        }
+        write!(f, "{:.0} words/sec", self.words_per_sec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeated_runs_agree_on_the_word_count() {
+        let config = Config::default();
+        let bench = tokenize_throughput("<p>Hello&nbsp;<b>world</b></p>\n\nOne more line.", &config, 5).unwrap();
+        assert_eq!(bench.runs, 5);
+        assert_eq!(bench.words, 5);
+        assert_eq!(bench.stages.len(), 3);
+        assert_eq!(bench.total(), bench.stages.iter().map(|(_, time)| *time).sum());
+    }
+
+    #[test]
+    fn zero_runs_still_run_once() {
+        let bench = tokenize_throughput("just three words", &Config::default(), 0).unwrap();
+        assert_eq!(bench.runs, 1);
+        assert_eq!(bench.words, 3);
+        assert!(bench.to_string().ends_with("words/sec"));
+    }
+}
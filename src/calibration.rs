@@ -0,0 +1,123 @@
+use crate::config::Config;
+use anyhow::Result;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Standard passage read during calibration, so runs at different speeds compare fairly
+pub const PASSAGE: &str = "\
+The lighthouse keeper climbed the spiral stairs each evening at dusk. \
+From the lamp room he could see the whole bay, the fishing boats returning \
+to the harbor, and the dark line of hills beyond the town. His work was simple \
+but it mattered: the light had to be lit on time, the lens kept clean, and \
+the log filled in before midnight. In winter the storms came in from the west \
+and rattled the windows for days. On those nights he checked the lamp every \
+hour and listened to the radio for ships in trouble. In summer the evenings \
+were long and calm, and visitors sometimes walked out along the pier to ask \
+him about the old days. He told them about the wreck of the Mary Ellen, about \
+the year the harbor froze, and about his father, who had kept the same light \
+for forty years before him.";
+
+/// Comprehension ratings run from 1 (took in little) to this
+pub const MAX_RATING: u8 = 5;
+
+/// Average rating at or above which a speed counts as comfortable
+const GOOD_RATING: f32 = 4.0;
+
+/// One calibration read: the speed and the reader's own comprehension rating
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CalibrationRun {
+    pub wpm: u32,
+    pub comprehension: u8,
+}
+
+impl CalibrationRun {
+    /// Log line, `wpm,comprehension`
+    pub fn to_line(self) -> String {
+        format!("{},{}", self.wpm, self.comprehension)
+    }
+
+    pub fn from_line(line: &str) -> Option<Self> {
+        let (wpm, comprehension) = line.trim().split_once(',')?;
+        let run = Self {
+            wpm: wpm.trim().parse().ok()?,
+            comprehension: comprehension.trim().parse().ok()?,
+        };
+        (1..=MAX_RATING).contains(&run.comprehension).then_some(run)
+    }
+}
+
+fn log_path() -> Result<PathBuf> {
+    Ok(Config::app_dir()?.join("calibration.csv"))
+}
+
+/// Append a run to the calibration log
+pub fn record(run: CalibrationRun) -> Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(log_path()?)?;
+    writeln!(file, "{}", run.to_line())?;
+    Ok(())
+}
+
+/// All logged runs, skipping lines that don't parse
+pub fn load_runs() -> Result<Vec<CalibrationRun>> {
+    let path = log_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    Ok(fs::read_to_string(path)?.lines().filter_map(CalibrationRun::from_line).collect())
+}
+
+/// Fastest speed whose average rating is still good; failing that, the speed
+/// with the best average rating. None without any runs.
+pub fn suggest_wpm(runs: &[CalibrationRun]) -> Option<u32> {
+    let mut speeds: Vec<u32> = runs.iter().map(|r| r.wpm).collect();
+    speeds.sort_unstable();
+    speeds.dedup();
+
+    let average = |wpm: u32| {
+        let ratings: Vec<f32> = runs.iter().filter(|r| r.wpm == wpm).map(|r| r.comprehension as f32).collect();
+        ratings.iter().sum::<f32>() / ratings.len() as f32
+    };
+
+    speeds
+        .iter()
+        .rev()
+        .find(|&&wpm| average(wpm) >= GOOD_RATING)
+        .or_else(|| speeds.iter().max_by(|&&a, &&b| average(a).total_cmp(&average(b))))
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(wpm: u32, comprehension: u8) -> CalibrationRun {
+        CalibrationRun { wpm, comprehension }
+    }
+
+    #[test]
+    fn log_lines_round_trip() {
+        assert_eq!(run(450, 4).to_line(), "450,4");
+        assert_eq!(CalibrationRun::from_line(" 450, 4 \n"), Some(run(450, 4)));
+    }
+
+    #[test]
+    fn bad_log_lines_are_skipped() {
+        for line in ["", "450", "fast,4", "450,0", "450,6", "450,4,1"] {
+            assert_eq!(CalibrationRun::from_line(line), None, "{:?}", line);
+        }
+    }
+
+    #[test]
+    fn suggests_the_fastest_speed_still_understood() {
+        let runs = [run(300, 5), run(400, 5), run(400, 3), run(500, 4), run(500, 4), run(600, 2)];
+        assert_eq!(suggest_wpm(&runs), Some(500));
+    }
+
+    #[test]
+    fn without_a_good_speed_suggests_the_best_rated() {
+        let runs = [run(300, 3), run(500, 2), run(700, 1)];
+        assert_eq!(suggest_wpm(&runs), Some(300));
+        assert_eq!(suggest_wpm(&[]), None);
+    }
+}
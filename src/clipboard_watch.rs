@@ -0,0 +1,112 @@
+use std::time::{Duration, Instant};
+
+/// Decides when a clipboard change should start reading in auto-read mode.
+///
+/// Fed the clipboard contents on every poll; reports a trigger once new text
+/// has stayed unchanged for the debounce period and is long enough to be
+/// worth reading (so copying a single word or a password doesn't pop up).
+pub struct ClipboardWatcher {
+    last_seen: Option<String>,
+    changed_at: Option<Instant>, // When the current text appeared, if it qualifies
+    min_chars: usize,
+    debounce: Duration,
+}
+
+impl ClipboardWatcher {
+    pub fn new(min_chars: usize, debounce: Duration) -> Self {
+        Self {
+            last_seen: None,
+            changed_at: None,
+            min_chars,
+            debounce,
+        }
+    }
+
+    /// Record the current clipboard text; true when it should trigger reading
+    pub fn observe(&mut self, text: &str, now: Instant) -> bool {
+        if self.last_seen.as_deref() != Some(text) {
+            // The first observation is whatever was there at startup, never a trigger
+            let primed = self.last_seen.is_some();
+            self.last_seen = Some(text.to_string());
+            self.changed_at = (primed && text.trim().chars().count() >= self.min_chars).then_some(now);
+            return false;
+        }
+
+        match self.changed_at {
+            Some(since) if now.duration_since(since) >= self.debounce => {
+                self.changed_at = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Mark text Speeder itself put on (or already read from) the clipboard so it
+    /// doesn't trigger another read
+    pub fn ignore(&mut self, text: &str) {
+        self.last_seen = Some(text.to_string());
+        self.changed_at = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEBOUNCE: Duration = Duration::from_millis(300);
+
+    /// A watcher primed with `initial` as the clipboard text at startup
+    fn watcher(initial: &str, start: Instant) -> ClipboardWatcher {
+        let mut watcher = ClipboardWatcher::new(20, DEBOUNCE);
+        assert!(!watcher.observe(initial, start));
+        watcher
+    }
+
+    #[test]
+    fn startup_text_never_triggers() {
+        let start = Instant::now();
+        let mut watcher = watcher("A long paragraph already on the clipboard.", start);
+        assert!(!watcher.observe("A long paragraph already on the clipboard.", start + Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn new_text_triggers_once_after_the_debounce() {
+        let start = Instant::now();
+        let mut watcher = watcher("", start);
+        let text = "Some freshly copied text worth reading.";
+        assert!(!watcher.observe(text, start + Duration::from_millis(100)));
+        assert!(!watcher.observe(text, start + Duration::from_millis(300)));
+        assert!(watcher.observe(text, start + Duration::from_millis(400)));
+        assert!(!watcher.observe(text, start + Duration::from_millis(800)));
+    }
+
+    #[test]
+    fn short_copies_are_ignored() {
+        let start = Instant::now();
+        let mut watcher = watcher("", start);
+        let password = "hunter2-hunter2";
+        assert!(!watcher.observe(password, start));
+        assert!(!watcher.observe(password, start + Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn text_changing_again_restarts_the_debounce() {
+        let start = Instant::now();
+        let mut watcher = watcher("", start);
+        assert!(!watcher.observe("The first version of the copied text.", start));
+        let edited = "The second version of the copied text.";
+        assert!(!watcher.observe(edited, start + Duration::from_millis(250)));
+        assert!(!watcher.observe(edited, start + Duration::from_millis(400)));
+        assert!(watcher.observe(edited, start + Duration::from_millis(550)));
+    }
+
+    #[test]
+    fn ignored_text_does_not_trigger() {
+        let start = Instant::now();
+        let mut watcher = watcher("", start);
+        let sentence = "A sentence Speeder copied on its own.";
+        watcher.ignore(sentence);
+        assert!(!watcher.observe(sentence, start + Duration::from_millis(100)));
+        assert!(!watcher.observe(sentence, start + Duration::from_secs(1)));
+    }
+}
@@ -1,97 +1,1041 @@
+use crate::rsvp_engine::{RSVPEngine, WordKind};
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
 use std::fs;
 use std::path::PathBuf;
+use std::str::FromStr;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Config {
     pub speed: SpeedConfig,
     pub display: DisplayConfig,
     pub hotkeys: HotkeyConfig,
+    pub language: LanguageConfig,
+    pub timing: TimingConfig,
+    pub preprocess: PreprocessConfig,
+    pub behavior: BehaviorConfig,
+    pub auto_read_on_copy: bool,    // Start reading whenever new text is copied
+    pub auto_read_min_chars: usize, // Ignore copies shorter than this (single words, passwords)
+    pub study_checkpoint_sentences: u32, // Pause for a "continue?" every N sentences (0 = off)
+    pub read_on_launch: bool,       // Read the clipboard as soon as Speeder starts (also `--now`)
+    pub daily_goal: u64,            // Words to read each day, shown in the menubar tooltip (0 = off)
+    pub version: u32,               // Schema version of the file; older files are migrated on load
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SpeedConfig {
     pub target_wpm: u32,
     pub warmup_words: u32,  // Number of words to reach target speed
+    pub warmup_start: f32,  // Share of the target speed the warmup starts from
+    pub end_hold_ms: u64,   // How long the final word stays up before finishing
+    pub skim_stride: u32,   // Show every Nth word while skimming
+    pub min_visible_ms: u64, // Every shown word stays at least this long (0 = off)
+    pub min_display_ms: u64, // Shortest display time of any word, however fast the speed
+    pub max_display_ms: u64, // Longest display time of any word, however slow the speed
+    pub seek_keeps_phase: bool, // Short seeks keep the reading rhythm instead of restarting the dwell
+    pub rewind_on_resume: u32, // Words replayed when resuming from a pause (0 = off)
+    pub lead_in_ms: u64,    // Blank card shown before the first word (0 = off)
+    pub timebox_secs: u64,  // `T` paces the current text to finish in this long
+    pub chunk_size: usize,  // Words shown together per flash
+    pub adjust_step: u32,   // WPM change per press of a speed key
+    pub presets: Vec<u32>,  // Speeds the number keys 1-5 jump to
+    pub min_wpm: u32,       // Slowest speed the speed keys and presets go to
+    pub max_wpm: u32,       // Fastest speed the speed keys and presets go to
+    pub smooth_changes: bool, // Ease into a new speed over a few words instead of switching at once
 }
 
 impl SpeedConfig {
-    /// Calculate start WPM as 75% of target for gradual warmup
+    pub const MAX_WARMUP_WORDS: u32 = 200;
+    pub const MIN_WARMUP_START: f32 = 0.25;
+    /// WPM change per press of a speed key with Shift held
+    pub const FINE_ADJUST_STEP: u32 = 5;
+    /// WPM change per press of a speed key with Alt (Option) held
+    pub const COARSE_ADJUST_STEP: u32 = 100;
+    /// Presets past this many have no number key
+    pub const MAX_PRESETS: usize = 5;
+
+    /// WPM change for one press of a speed key: `adjust_step`, or the fine or
+    /// coarse step when that modifier is held
+    pub fn step(&self, fine: bool, coarse: bool) -> u32 {
+        if coarse {
+            Self::COARSE_ADJUST_STEP
+        } else if fine {
+            Self::FINE_ADJUST_STEP
+        } else {
+            self.adjust_step
+        }
+    }
+
+    /// Speed the warmup starts from, `warmup_start` of the target (but no slower than `min_wpm`)
     pub fn start_wpm(&self) -> u32 {
-        (self.target_wpm as f32 * 0.75) as u32
+        ((self.target_wpm as f32 * self.warmup_start) as u32).clamp(self.min_wpm.min(self.target_wpm), self.target_wpm)
     }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct DisplayConfig {
     pub font_size: f32,
-    pub orp_position: f32,
+    pub orp_position: f32,       // Focus point as a share of the word, with `orp_mode = "proportional"`
+    pub orp_mode: OrpMode,
+    pub brightness: f32, // 1.0 = stock colors, lower toward black, higher toward white
+    pub focus_trainer: FocusTrainerConfig,
+    pub progress_basis: ProgressBasis,
+    pub window_width: f32,
+    pub window_height: f32,
+    pub proportional_font: bool, // Render words in the proportional font instead of monospace
+    pub optical_center: bool,    // Proportional only: center the focus glyph itself on the pin
+    pub pause_cue: bool,         // Tint a small bar warmer when a long pause is coming up
+    pub hud: bool,               // Keep position, speed and progress visible while reading
+    pub position_style: PositionStyle,
+    pub click_through: bool,     // Clicks pass through the reader unless Option is held (macOS)
+    pub focus_colors: BTreeMap<WordKind, [u8; 3]>, // Focus letter RGB by kind of word
+    pub context_words: usize,    // Dim words shown on each side of the current one (0 = off), and recalled by `B`
+    pub fade_read_context: bool, // Already-read context fades with distance, to discourage looking back
+    pub peek_on_pause: bool,     // While paused, show the whole current sentence
+    pub max_word_chars: usize,   // Longer tokens (URLs, hashes) are shown in pieces
+    pub theme: Theme,
+    pub background_color: String, // Hex such as "#141419", overriding the theme's (empty = theme's)
+    pub text_color: String,
+    pub border_color: String,
+    pub background_opacity: f32, // 1.0 = solid card, lower lets what's behind the reader show through
+}
+
+/// How the focus character is chosen within a word
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OrpMode {
+    #[default]
+    Table,        // By word length: 2nd letter of a 4-letter word, 3rd of a 7-letter one, ...
+    Proportional, // `orp_position` of the way into the word
+}
+
+/// What the progress bar measures
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProgressBasis {
+    #[default]
+    Words, // Share of words read
+    Time,  // Share of expected reading time elapsed
+}
+
+/// How the HUD reports the reading position
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PositionStyle {
+    #[default]
+    Elapsed,   // "142/980"
+    Remaining, // "838 left", or time left when progress is by time
+}
+
+impl ProgressBasis {
+    /// The other basis, for flipping at runtime
+    pub fn toggled(self) -> Self {
+        match self {
+            ProgressBasis::Words => ProgressBasis::Time,
+            ProgressBasis::Time => ProgressBasis::Words,
+        }
+    }
+
+    /// Short label shown next to the bar
+    pub fn label(self) -> &'static str {
+        match self {
+            ProgressBasis::Words => "words",
+            ProgressBasis::Time => "time",
+        }
+    }
+}
+
+impl DisplayConfig {
+    pub const MIN_BRIGHTNESS: f32 = 0.3;
+    pub const MAX_BRIGHTNESS: f32 = 1.7;
+    pub const MIN_WINDOW_WIDTH: f32 = 300.0;
+    pub const MIN_WINDOW_HEIGHT: f32 = 60.0;
+    pub const MIN_FONT_SIZE: f32 = 16.0;
+    pub const MAX_FONT_SIZE: f32 = 96.0;
+    pub const MIN_WORD_CHARS: usize = 8;
+    /// Fewest recent words holding `B` recalls, however few `context_words` are shown
+    pub const MIN_REVIEW_WORDS: usize = 5;
+    pub const DEFAULT_FOCUS_COLOR: [u8; 3] = [255, 100, 100];
+
+    /// Focus letter color for `kind`; kinds missing from `focus_colors` use the normal one
+    pub fn focus_color(&self, kind: WordKind) -> [u8; 3] {
+        self.focus_colors
+            .get(&kind)
+            .or_else(|| self.focus_colors.get(&WordKind::Normal))
+            .copied()
+            .unwrap_or(Self::DEFAULT_FOCUS_COLOR)
+    }
+
+    /// Clamp a requested window size to the smallest usable reader
+    pub fn clamp_window_size(width: f32, height: f32) -> (f32, f32) {
+        (width.max(Self::MIN_WINDOW_WIDTH), height.max(Self::MIN_WINDOW_HEIGHT))
+    }
+
+    /// Font size the reader layout is measured against; spacing scales from it
+    pub const BASE_FONT_SIZE: f32 = 34.0;
+
+    /// How much larger than the base layout words are drawn
+    pub fn font_scale(&self) -> f32 {
+        self.font_size / Self::BASE_FONT_SIZE
+    }
+
+    /// Recent words holding `B` shows above the current one: the `context_words` before
+    /// it, but at least `MIN_REVIEW_WORDS`, so it works with context off too
+    pub fn review_words(&self) -> usize {
+        self.context_words.max(Self::MIN_REVIEW_WORDS)
+    }
+
+    /// Width an average context word and its space take at the base font size
+    const CONTEXT_WORD_WIDTH: f32 = 130.0;
+
+    /// Window size to open with: the saved size, grown if words at `font_size` (with
+    /// `context_words` on each side) wouldn't fit
+    pub fn reader_size(&self) -> (f32, f32) {
+        let scale = self.font_scale();
+        let context = 2.0 * self.context_words as f32 * Self::CONTEXT_WORD_WIDTH;
+        (self.window_width.max((620.0 + context) * scale), self.window_height.max(90.0 * scale))
+    }
+
+    /// Record a new window size (clamped) to be used at next launch
+    pub fn set_window_size(&mut self, width: f32, height: f32) {
+        (self.window_width, self.window_height) = Self::clamp_window_size(width, height);
+    }
+
+    /// The theme's colors with any `*_color` overrides applied
+    pub fn palette(&self) -> Palette {
+        let mut palette = self.theme.palette();
+        for (hex, color) in [
+            (&self.background_color, &mut palette.background),
+            (&self.text_color, &mut palette.text),
+            (&self.border_color, &mut palette.border),
+        ] {
+            if let Ok(rgb) = parse_hex_color(hex) {
+                *color = rgb;
+            }
+        }
+        palette
+    }
+}
+
+/// Color scheme of the reader card
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Theme {
+    #[default]
+    Dark,  // Light text on a near-black card
+    Light, // Dark text on an off-white card
+}
+
+/// Colors the reader is drawn in, as RGB
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Palette {
+    pub background: [u8; 3],
+    pub text: [u8; 3],
+    pub border: [u8; 3],
+    pub track: [u8; 3], // Unfilled part of the progress bar
+}
+
+impl Theme {
+    pub const ALL: [Theme; 2] = [Theme::Dark, Theme::Light];
+
+    pub fn palette(self) -> Palette {
+        match self {
+            Theme::Dark => Palette {
+                background: [20, 20, 25],
+                text: [200, 200, 210],
+                border: [60, 60, 70],
+                track: [40, 40, 50],
+            },
+            Theme::Light => Palette {
+                background: [245, 244, 240],
+                text: [40, 40, 48],
+                border: [200, 200, 205],
+                track: [215, 215, 220],
+            },
+        }
+    }
+
+    /// Name shown in the settings window
+    pub fn label(self) -> &'static str {
+        match self {
+            Theme::Dark => "Dark",
+            Theme::Light => "Light",
+        }
+    }
+}
+
+/// Parse a color written as "#rrggbb" or "#rgb" (the '#' is optional)
+pub fn parse_hex_color(s: &str) -> Result<[u8; 3]> {
+    let hex = s.trim().trim_start_matches('#');
+    let digits: Vec<u8> = hex
+        .chars()
+        .map(|c| c.to_digit(16).map(|d| d as u8))
+        .collect::<Option<_>>()
+        .ok_or_else(|| anyhow::anyhow!("{:?} is not a hex color", s))?;
+    match digits[..] {
+        [r, g, b] => Ok([r * 17, g * 17, b * 17]),
+        [r1, r2, g1, g2, b1, b2] => Ok([r1 * 16 + r2, g1 * 16 + g2, b1 * 16 + b2]),
+        _ => anyhow::bail!("{:?} is not a hex color like \"#ff6464\"", s),
+    }
+}
+
+/// Briefly dims the letters around the focus point at random moments
+/// so the reader learns to rely on the fixation point
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FocusTrainerConfig {
+    pub enabled: bool,
+    pub interval_ms: u64, // Average gap between dimmed periods
+    pub duration_ms: u64, // How long each dimmed period lasts
+    pub seed: u64,        // Same seed gives the same schedule
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct HotkeyConfig {
     pub start_reading: Vec<String>,
+    pub start_reading_fallback: Vec<String>, // Tried when start_reading can't be registered (empty = none)
     pub pause_resume: Vec<String>,
     pub speed_up: Vec<String>,
     pub speed_down: Vec<String>,
     pub quit: Vec<String>,
+    // In-window arrow key actions: speed_up, speed_down, seek_back, seek_forward
+    pub arrow_up: String,
+    pub arrow_down: String,
+    pub arrow_left: String,
+    pub arrow_right: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LanguageConfig {
+    pub auto_detect: bool, // Pick tokenization/direction from the text's script
+    pub cjk_cpm: u32,      // Characters per minute for CJK text
+}
+
+/// Extra pauses taken from the text's layout
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TimingConfig {
+    pub respect_line_breaks: bool,    // Pause at single line breaks (poetry, code)
+    pub line_break_pause_factor: f32, // Dwell multiplier for the last word of a line
+    pub paragraph_pause_factor: f32,  // Dwell multiplier for the last word of a paragraph
+    pub sentence_terminators: String, // Characters that end a sentence (pause and sentence count)
+    pub sentence_pause_factor: f32,   // Dwell multiplier for a word ending a sentence
+    pub comma_pause_factor: f32,      // Dwell multiplier for a word with a comma
+    pub terminator_run_bonus: f32,    // Extra pause per repeated terminator ("?!", "..."), up to three
+    pub detect_headings: bool,        // Treat all-caps lines as headings: shown larger, held longer
+    pub heading_factor: f32,          // Dwell multiplier for heading words
+    pub detect_spaced_emphasis: bool, // Join letter-spaced words ("N O T") into one emphasized word
+    pub emphasis_factor: f32,         // Dwell multiplier for those emphasized words
+    pub max_pause_factor: f32,        // Cap on a word's combined dwell multiplier
+    pub symbol_dwell_ms: u64,         // Fixed time for emoji/symbol-only tokens (0 = timed like words)
+    #[serde(alias = "timing_strategy")]
+    pub mode: TimingMode,             // Pacing strategy: how a word's look affects its dwell
+    pub cycle_modes: Vec<TimingMode>, // Modes `M` steps through while reading
+}
+
+/// The timing strategy words are paced with (see `rsvp_engine::TimingStrategy`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimingMode {
+    Constant,   // Every word gets the same time, ignoring length, punctuation and layout
+    #[default]
+    #[serde(alias = "default")]
+    CharLength, // Longer words get gently more time; punctuation and layout pause
+    Syllables,  // Time grows with each (estimated) syllable past the first
+    Cpm,        // Time proportional to characters, like a characters-per-minute pace
+}
+
+impl TimingMode {
+    pub const ALL: [TimingMode; 4] = [
+        TimingMode::Constant,
+        TimingMode::CharLength,
+        TimingMode::Syllables,
+        TimingMode::Cpm,
+    ];
+
+    /// The mode after `self` in `modes`, wrapping around (the first one if `self` isn't listed)
+    pub fn next_in(self, modes: &[TimingMode]) -> TimingMode {
+        let next = modes.iter().position(|&m| m == self).map_or(0, |i| i + 1);
+        modes.get(next % modes.len().max(1)).copied().unwrap_or(self)
+    }
+}
+
+/// Filters applied to copied text before it is split into words
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PreprocessConfig {
+    pub code_comment_lang: CodeCommentLang, // Read only the comments of code in this language
+    pub strip_html: bool,                   // Read copied HTML markup as its text: tags dropped, entities decoded
+    pub strip_markdown: bool,               // Drop Markdown syntax: emphasis, heading hashes, bullets, link targets
+}
+
+impl Default for PreprocessConfig {
+    fn default() -> Self {
+        Self {
+            code_comment_lang: CodeCommentLang::Off,
+            strip_html: true,
+            strip_markdown: false,
+        }
+    }
+}
+
+/// Comment syntax for the code comment filter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CodeCommentLang {
+    #[default]
+    Off,        // Read the whole text
+    Rust,       // `//` and `/* */`
+    C,          // `//` and `/* */`
+    JavaScript, // `//` and `/* */`
+    Python,     // `#`
+    Shell,      // `#`
+}
+
+/// How the app behaves across sessions
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BehaviorConfig {
+    pub crash_recovery: bool,        // Keep a snapshot of the current read and reopen it after a crash
+    pub resume_in_longer_text: bool, // Keep your place when the new text contains the previous one
+    pub on_focus_loss: FocusLossAction, // What happens to a read when the reader loses focus
+    pub remember_position: bool,     // Reopen the reader where it was last, instead of centered
+    pub window_position: Option<(f32, f32)>, // Top-left corner of the reader when reading last stopped
+}
+
+/// What switching away from the reader does to the current read
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FocusLossAction {
+    #[default]
+    Stop,  // End the read (its position is remembered)
+    Pause, // Hide the reader paused; the hotkey brings it back where it was
+}
+
+impl LanguageConfig {
+    /// Calculate start CPM as 75% of target, matching the WPM warmup
+    pub fn start_cjk_cpm(&self) -> u32 {
+        (self.cjk_cpm as f32 * 0.75) as u32
+    }
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
-            speed: SpeedConfig {
-                target_wpm: 400,
-                warmup_words: 10,  // Reach full speed after 10 words
-            },
-            display: DisplayConfig {
-                font_size: 48.0,
-                orp_position: 0.33,
-            },
-            hotkeys: HotkeyConfig {
-                start_reading: vec!["cmd".to_string(), "shift".to_string(), "r".to_string()],
-                pause_resume: vec!["space".to_string()],
-                speed_up: vec!["up".to_string()],
-                speed_down: vec!["down".to_string()],
-                quit: vec!["escape".to_string()],
-            },
+            speed: SpeedConfig::default(),
+            display: DisplayConfig::default(),
+            hotkeys: HotkeyConfig::default(),
+            language: LanguageConfig::default(),
+            timing: TimingConfig::default(),
+            preprocess: PreprocessConfig::default(),
+            behavior: BehaviorConfig::default(),
+            auto_read_on_copy: false,
+            auto_read_min_chars: 40,
+            study_checkpoint_sentences: 0,
+            read_on_launch: false,
+            daily_goal: 0,
+            version: Config::VERSION,
+        }
+    }
+}
+
+impl Default for SpeedConfig {
+    fn default() -> Self {
+        Self {
+            target_wpm: 400,
+            warmup_words: 10,  // Reach full speed after 10 words
+            warmup_start: 0.75,
+            end_hold_ms: 500,
+            skim_stride: 3,
+            min_visible_ms: 0,
+            min_display_ms: 60,
+            max_display_ms: 2000,
+            seek_keeps_phase: false,
+            rewind_on_resume: 0,
+            lead_in_ms: 0,
+            timebox_secs: 300,
+            chunk_size: 1,
+            adjust_step: 25,
+            presets: vec![200, 300, 400, 600, 800],
+            min_wpm: RSVPEngine::MIN_WPM,
+            max_wpm: RSVPEngine::MAX_WPM,
+            smooth_changes: false,
+        }
+    }
+}
+
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        Self {
+            font_size: 48.0,
+            orp_position: 0.33,
+            orp_mode: OrpMode::Table,
+            brightness: 1.0,
+            focus_trainer: FocusTrainerConfig::default(),
+            progress_basis: ProgressBasis::Words,
+            window_width: 700.0,
+            window_height: 90.0,
+            proportional_font: false,
+            optical_center: false,
+            pause_cue: false,
+            hud: false,
+            position_style: PositionStyle::Elapsed,
+            click_through: false,
+            focus_colors: WordKind::ALL
+                .iter()
+                .map(|&kind| (kind, DisplayConfig::DEFAULT_FOCUS_COLOR))
+                .collect(),
+            context_words: 0,
+            fade_read_context: false,
+            peek_on_pause: false,
+            max_word_chars: 20,
+            theme: Theme::Dark,
+            background_color: String::new(),
+            text_color: String::new(),
+            border_color: String::new(),
+            background_opacity: 1.0,
+        }
+    }
+}
+
+impl Default for FocusTrainerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_ms: 4000,
+            duration_ms: 800,
+            seed: 0,
+        }
+    }
+}
+
+impl Default for HotkeyConfig {
+    fn default() -> Self {
+        Self {
+            start_reading: vec!["cmd".to_string(), "ctrl".to_string(), "r".to_string()],
+            start_reading_fallback: Vec::new(),
+            pause_resume: vec!["space".to_string()],
+            speed_up: vec!["up".to_string()],
+            speed_down: vec!["down".to_string()],
+            quit: vec!["escape".to_string()],
+            arrow_up: "speed_up".to_string(),
+            arrow_down: "speed_down".to_string(),
+            arrow_left: "seek_back".to_string(),
+            arrow_right: "seek_forward".to_string(),
+        }
+    }
+}
+
+impl Default for LanguageConfig {
+    fn default() -> Self {
+        Self {
+            auto_detect: true,
+            cjk_cpm: 500,
+        }
+    }
+}
+
+impl Default for TimingConfig {
+    fn default() -> Self {
+        Self {
+            respect_line_breaks: false,
+            line_break_pause_factor: 1.3,
+            paragraph_pause_factor: 2.0,
+            sentence_terminators: ".!?;".to_string(),
+            sentence_pause_factor: 1.4,
+            comma_pause_factor: 1.15,
+            terminator_run_bonus: 0.2,
+            detect_headings: false,
+            heading_factor: 1.5,
+            detect_spaced_emphasis: false,
+            emphasis_factor: 1.5,
+            max_pause_factor: 3.0,
+            symbol_dwell_ms: 0,
+            mode: TimingMode::CharLength,
+            cycle_modes: TimingMode::ALL.to_vec(),
         }
     }
 }
 
 impl Config {
+    /// Schema version written by this build. Bump it with a step in `migrate` whenever a
+    /// field is renamed, moved or removed.
+    pub const VERSION: u32 = 2;
+
+    /// Parse a config file of any known version, along with the version it was written at
+    fn parse_migrating(s: &str) -> Result<(Self, u32)> {
+        let mut table: toml::Table = toml::from_str(s)?;
+        // Files from before the version field are version 1, and so is anything that
+        // isn't a real version (0, negative, not a number): at worst a no-op upgrade
+        let file_version = match table.get("version") {
+            Some(value) => match value.as_integer().and_then(|v| u32::try_from(v).ok()).filter(|&v| v >= 1) {
+                Some(version) => version,
+                None => {
+                    log::warn!("Invalid config version {}, reading it as version 1", value);
+                    1
+                }
+            },
+            None => 1,
+        };
+        if file_version > Self::VERSION {
+            log::warn!(
+                "Config version {} is newer than this build ({}); reading it as is",
+                file_version,
+                Self::VERSION
+            );
+        } else {
+            migrate(&mut table, file_version);
+        }
+
+        let mut config: Config = table.try_into()?;
+        config.clamp_to_limits();
+        Ok((config, file_version))
+    }
+
     pub fn load() -> Result<Self> {
         let config_path = Self::config_path()?;
 
         if !config_path.exists() {
             let default_config = Config::default();
-            let toml = toml::to_string_pretty(&default_config)?;
-            fs::write(&config_path, toml)?;
+            fs::write(&config_path, default_config.to_string())?;
             return Ok(default_config);
         }
 
-        let contents = fs::read_to_string(&config_path)?;
-        let config: Config = toml::from_str(&contents)?;
+        let (config, file_version) = Self::parse_migrating(&fs::read_to_string(&config_path)?)?;
+        if file_version < Self::VERSION {
+            log::info!("Upgraded config from version {} to {}", file_version, Self::VERSION);
+            config.save()?;
+        }
         Ok(config)
     }
 
     pub fn save(&self) -> Result<()> {
         let config_path = Self::config_path()?;
-        let toml = toml::to_string_pretty(self)?;
-        fs::write(&config_path, toml)?;
+        fs::write(&config_path, self.to_string())?;
         Ok(())
     }
 
-    fn config_path() -> Result<PathBuf> {
-        let config_dir = dirs::config_dir()
-            .ok_or_else(|| anyhow::anyhow!("Could not find config directory"))?;
-        let app_dir = config_dir.join("speeder");
+    /// Pull out-of-range values back into the ranges the reader supports. Applied to
+    /// loaded files and to edits from the settings window alike.
+    pub fn clamp_to_limits(&mut self) {
+        let speed = &mut self.speed;
+        speed.min_wpm = speed.min_wpm.clamp(RSVPEngine::LOWEST_WPM, RSVPEngine::HIGHEST_WPM);
+        speed.max_wpm = speed.max_wpm.min(RSVPEngine::HIGHEST_WPM);
+        if speed.max_wpm < speed.min_wpm {
+            log::warn!("speed.max_wpm {} is below speed.min_wpm {}, using {}", speed.max_wpm, speed.min_wpm, speed.min_wpm);
+            speed.max_wpm = speed.min_wpm;
+        }
+        let target_wpm = speed.target_wpm.clamp(speed.min_wpm, speed.max_wpm);
+        if target_wpm != speed.target_wpm {
+            log::warn!(
+                "speed.target_wpm {} is outside {}-{}, using {}",
+                speed.target_wpm,
+                speed.min_wpm,
+                speed.max_wpm,
+                target_wpm
+            );
+            speed.target_wpm = target_wpm;
+        }
+        speed.warmup_words = speed.warmup_words.min(SpeedConfig::MAX_WARMUP_WORDS);
+        speed.warmup_start = speed.warmup_start.clamp(SpeedConfig::MIN_WARMUP_START, 1.0);
+        speed.adjust_step = speed.adjust_step.max(1);
+        if speed.presets.len() > SpeedConfig::MAX_PRESETS {
+            log::warn!("Ignoring speed.presets past the first {}", SpeedConfig::MAX_PRESETS);
+            speed.presets.truncate(SpeedConfig::MAX_PRESETS);
+        }
+        for wpm in &mut speed.presets {
+            *wpm = (*wpm).clamp(speed.min_wpm, speed.max_wpm);
+        }
+
+        // Pauses only ever lengthen a word; below 1.0 they would shorten it, or make its
+        // time negative
+        let timing = &mut self.timing;
+        for (key, factor) in [
+            ("line_break_pause_factor", &mut timing.line_break_pause_factor),
+            ("paragraph_pause_factor", &mut timing.paragraph_pause_factor),
+            ("sentence_pause_factor", &mut timing.sentence_pause_factor),
+            ("comma_pause_factor", &mut timing.comma_pause_factor),
+            ("heading_factor", &mut timing.heading_factor),
+            ("emphasis_factor", &mut timing.emphasis_factor),
+            ("max_pause_factor", &mut timing.max_pause_factor),
+        ] {
+            if factor.is_nan() || *factor < 1.0 {
+                log::warn!("timing.{} {} is below 1.0, using 1.0", key, factor);
+                *factor = 1.0;
+            }
+        }
+        if timing.terminator_run_bonus.is_nan() || timing.terminator_run_bonus < 0.0 {
+            log::warn!("timing.terminator_run_bonus {} is negative, using 0.0", timing.terminator_run_bonus);
+            timing.terminator_run_bonus = 0.0;
+        }
+
+        let display = &mut self.display;
+        display.font_size = display.font_size.clamp(DisplayConfig::MIN_FONT_SIZE, DisplayConfig::MAX_FONT_SIZE);
+        display.orp_position = display.orp_position.clamp(0.0, 1.0);
+        display.brightness = display.brightness.clamp(DisplayConfig::MIN_BRIGHTNESS, DisplayConfig::MAX_BRIGHTNESS);
+        display.max_word_chars = display.max_word_chars.max(DisplayConfig::MIN_WORD_CHARS);
+        display.background_opacity = display.background_opacity.clamp(0.0, 1.0);
+        for (key, hex) in [
+            ("background_color", &mut display.background_color),
+            ("text_color", &mut display.text_color),
+            ("border_color", &mut display.border_color),
+        ] {
+            if !hex.is_empty() {
+                if let Err(e) = parse_hex_color(hex) {
+                    log::warn!("Ignoring display.{}: {}", key, e);
+                    hex.clear();
+                }
+            }
+        }
+    }
+
+    /// Directory holding the config and other app data.
+    /// `SPEEDER_CONFIG_DIR` overrides the platform default (handy for tests and scripts).
+    pub fn app_dir() -> Result<PathBuf> {
+        let app_dir = match std::env::var_os("SPEEDER_CONFIG_DIR") {
+            Some(dir) => PathBuf::from(dir),
+            None => dirs::config_dir()
+                .ok_or_else(|| anyhow::anyhow!("Could not find config directory"))?
+                .join("speeder"),
+        };
 
         if !app_dir.exists() {
             fs::create_dir_all(&app_dir)?;
         }
 
-        Ok(app_dir.join("config.toml"))
+        Ok(app_dir)
+    }
+
+    fn config_path() -> Result<PathBuf> {
+        Ok(Self::app_dir()?.join("config.toml"))
+    }
+}
+
+/// Upgrade a parsed config file from `from_version`, one version at a time, to `Config::VERSION`
+fn migrate(table: &mut toml::Table, from_version: u32) {
+    for version in from_version.max(1)..Config::VERSION {
+        match version {
+            1 => migrate_from_v1(table),
+            _ => log::warn!("No config migration from version {}", version),
+        }
+    }
+    table.insert("version".to_string(), toml::Value::Integer(Config::VERSION as i64));
+}
+
+/// Version 1 files were written with `start_reading = ["cmd", "shift", "r"]`, which was
+/// never registered (the hotkey was hardcoded to Cmd+Ctrl+R). Keep those users on
+/// Cmd+Ctrl+R rather than moving them to the browser's hard-reload shortcut.
+fn migrate_from_v1(table: &mut toml::Table) {
+    let Some(hotkeys) = table.get_mut("hotkeys").and_then(toml::Value::as_table_mut) else {
+        return;
+    };
+    let old_default = ["cmd", "shift", "r"].map(|key| toml::Value::String(key.to_string()));
+    if hotkeys.get("start_reading").and_then(toml::Value::as_array).is_some_and(|keys| keys[..] == old_default) {
+        let new_default = HotkeyConfig::default().start_reading.into_iter().map(toml::Value::String).collect();
+        hotkeys.insert("start_reading".to_string(), toml::Value::Array(new_default));
+    }
+}
+
+/// Parse a config from TOML without touching the filesystem
+impl FromStr for Config {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(Self::parse_migrating(s)?.0)
+    }
+}
+
+/// Serialize the config as pretty TOML, the format `load` reads back
+impl fmt::Display for Config {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let toml = toml::to_string_pretty(self).map_err(|_| fmt::Error)?;
+        f.write_str(&toml)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_file_gives_defaults() {
+        let config: Config = "".parse().unwrap();
+        let defaults = Config::default();
+        assert_eq!(config.speed.target_wpm, defaults.speed.target_wpm);
+        assert_eq!(config.display.font_size, defaults.display.font_size);
+        assert_eq!(config.hotkeys.start_reading, defaults.hotkeys.start_reading);
+    }
+
+    #[test]
+    fn config_round_trips_through_toml() {
+        let mut config = Config::default();
+        config.speed.target_wpm = 550;
+        config.display.font_size = 64.0;
+        config.display.theme = Theme::Light;
+        let parsed: Config = config.to_string().parse().unwrap();
+        assert_eq!(parsed.speed.target_wpm, 550);
+        assert_eq!(parsed.display.font_size, 64.0);
+        assert_eq!(parsed.display.theme, Theme::Light);
+        assert_eq!(parsed.to_string(), config.to_string());
+    }
+
+    #[test]
+    fn out_of_range_values_are_clamped_on_parse() {
+        let config: Config = "[speed]\ntarget_wpm = 5000\n[display]\nfont_size = 1.0\n".parse().unwrap();
+        assert_eq!(config.speed.target_wpm, config.speed.max_wpm);
+        assert_eq!(config.display.font_size, DisplayConfig::MIN_FONT_SIZE);
+    }
+
+    #[test]
+    fn negative_pause_factors_are_clamped_on_parse() {
+        let file = "[timing]\nsentence_pause_factor = -1.0\ncomma_pause_factor = 0.5\nheading_factor = -2.0\nterminator_run_bonus = -0.3\n";
+        let config: Config = file.parse().unwrap();
+        assert_eq!(config.timing.sentence_pause_factor, 1.0);
+        assert_eq!(config.timing.comma_pause_factor, 1.0);
+        assert_eq!(config.timing.heading_factor, 1.0);
+        assert_eq!(config.timing.terminator_run_bonus, 0.0);
+        assert_eq!(config.timing.paragraph_pause_factor, TimingConfig::default().paragraph_pause_factor);
+    }
+
+    #[test]
+    fn max_pause_factor_is_at_least_one() {
+        for value in ["0.0", "-2.0", "0.5"] {
+            let config: Config = format!("[timing]\nmax_pause_factor = {}\n", value).parse().unwrap();
+            assert_eq!(config.timing.max_pause_factor, 1.0, "max_pause_factor = {}", value);
+        }
+        let config: Config = "[timing]\nmax_pause_factor = 2.5\n".parse().unwrap();
+        assert_eq!(config.timing.max_pause_factor, 2.5);
+    }
+
+    #[test]
+    fn malformed_toml_is_an_error() {
+        assert!("[speed\ntarget_wpm = ".parse::<Config>().is_err());
+        assert!("[speed]\ntarget_wpm = \"fast\"\n".parse::<Config>().is_err());
+    }
+
+    #[test]
+    fn window_size_is_clamped_to_the_minimum() {
+        assert_eq!(
+            DisplayConfig::clamp_window_size(10.0, 5.0),
+            (DisplayConfig::MIN_WINDOW_WIDTH, DisplayConfig::MIN_WINDOW_HEIGHT)
+        );
+        assert_eq!(DisplayConfig::clamp_window_size(800.0, 120.0), (800.0, 120.0));
+    }
+
+    #[test]
+    fn set_window_size_stores_the_clamped_size() {
+        let mut display = DisplayConfig::default();
+        display.set_window_size(100.0, 20.0);
+        assert_eq!(display.window_width, DisplayConfig::MIN_WINDOW_WIDTH);
+        assert_eq!(display.window_height, DisplayConfig::MIN_WINDOW_HEIGHT);
+        display.set_window_size(1200.0, 200.0);
+        assert_eq!((display.window_width, display.window_height), (1200.0, 200.0));
+    }
+
+    #[test]
+    fn saved_window_size_is_restored_after_a_round_trip() {
+        let mut config = Config::default();
+        config.display.font_size = DisplayConfig::BASE_FONT_SIZE;
+        config.display.set_window_size(1200.0, 200.0);
+        let parsed: Config = config.to_string().parse().unwrap();
+        assert_eq!(parsed.display.reader_size(), (1200.0, 200.0));
+    }
+
+    #[test]
+    fn missing_window_size_falls_back_to_the_default() {
+        let config: Config = "[display]\nfont_size = 40.0\n".parse().unwrap();
+        let defaults = DisplayConfig::default();
+        assert_eq!(config.display.window_width, defaults.window_width);
+        assert_eq!(config.display.window_height, defaults.window_height);
+    }
+
+    #[test]
+    fn review_recalls_context_words_but_at_least_five() {
+        let mut display = DisplayConfig::default();
+        assert_eq!(display.context_words, 0);
+        assert_eq!(display.review_words(), DisplayConfig::MIN_REVIEW_WORDS);
+        display.context_words = 8;
+        assert_eq!(display.review_words(), 8);
+    }
+
+    #[test]
+    fn version_1_file_is_migrated_to_current_version() {
+        let (config, file_version) = Config::parse_migrating("version = 1\n[speed]\ntarget_wpm = 500\n").unwrap();
+        assert_eq!(file_version, 1);
+        assert_eq!(config.version, Config::VERSION);
+        assert_eq!(config.speed.target_wpm, 500);
+        // Saved back at the current version
+        assert!(config.to_string().contains(&format!("version = {}", Config::VERSION)));
+    }
+
+    #[test]
+    fn file_without_version_is_version_1() {
+        let (config, file_version) = Config::parse_migrating("[speed]\ntarget_wpm = 500\n").unwrap();
+        assert_eq!(file_version, 1);
+        assert_eq!(config.version, Config::VERSION);
+    }
+
+    #[test]
+    fn invalid_versions_are_read_as_version_1() {
+        for version in ["0", "-1", "\"two\""] {
+            let (config, file_version) = Config::parse_migrating(&format!("version = {}\n", version)).unwrap();
+            assert_eq!(file_version, 1, "version = {}", version);
+            assert_eq!(config.version, Config::VERSION);
+        }
+    }
+
+    #[test]
+    fn version_1_stock_hotkey_becomes_cmd_ctrl_r() {
+        let file = "version = 1\n[hotkeys]\nstart_reading = [\"cmd\", \"shift\", \"r\"]\n";
+        let config: Config = file.parse().unwrap();
+        assert_eq!(config.hotkeys.start_reading, ["cmd", "ctrl", "r"]);
+    }
+
+    #[test]
+    fn version_1_custom_hotkey_is_kept() {
+        let file = "[hotkeys]\nstart_reading = [\"cmd\", \"alt\", \"space\"]\n";
+        let config: Config = file.parse().unwrap();
+        assert_eq!(config.hotkeys.start_reading, ["cmd", "alt", "space"]);
+    }
+
+    #[test]
+    fn current_version_hotkey_is_not_migrated() {
+        let file = format!("version = {}\n[hotkeys]\nstart_reading = [\"cmd\", \"shift\", \"r\"]\n", Config::VERSION);
+        let config: Config = file.parse().unwrap();
+        assert_eq!(config.hotkeys.start_reading, ["cmd", "shift", "r"]);
+    }
+
+    #[test]
+    fn toggled_progress_basis_is_saved() {
+        let mut config = Config::default();
+        assert_eq!(config.display.progress_basis, ProgressBasis::Words);
+        config.display.progress_basis = config.display.progress_basis.toggled();
+        assert_eq!(config.display.progress_basis, ProgressBasis::Time);
+        let reloaded: Config = config.to_string().parse().unwrap();
+        assert_eq!(reloaded.display.progress_basis, ProgressBasis::Time);
+        assert_eq!(ProgressBasis::Time.toggled(), ProgressBasis::Words);
+    }
+
+    #[test]
+    fn timing_modes_cycle_through_the_enabled_ones() {
+        let enabled = [TimingMode::CharLength, TimingMode::Cpm];
+        assert_eq!(TimingMode::CharLength.next_in(&enabled), TimingMode::Cpm);
+        assert_eq!(TimingMode::Cpm.next_in(&enabled), TimingMode::CharLength);
+        assert_eq!(TimingMode::Constant.next_in(&enabled), TimingMode::CharLength);
+        assert_eq!(TimingMode::Syllables.next_in(&[]), TimingMode::Syllables);
+    }
+
+    #[test]
+    fn timing_strategy_key_sets_the_mode() {
+        let config: Config = "[timing]\ntiming_strategy = \"constant\"\n".parse().unwrap();
+        assert_eq!(config.timing.mode, TimingMode::Constant);
+        let config: Config = "[timing]\ntiming_strategy = \"default\"\n".parse().unwrap();
+        assert_eq!(config.timing.mode, TimingMode::CharLength);
+    }
+
+    #[test]
+    fn focus_colors_default_to_red_and_fall_back_to_normal() {
+        let defaults = DisplayConfig::default();
+        for kind in WordKind::ALL {
+            assert_eq!(defaults.focus_color(kind), DisplayConfig::DEFAULT_FOCUS_COLOR);
+        }
+
+        let config: Config = "[display.focus_colors]\nnormal = [10, 20, 30]\nnumber = [0, 0, 255]\n"
+            .parse()
+            .unwrap();
+        assert_eq!(config.display.focus_color(WordKind::Number), [0, 0, 255]);
+        assert_eq!(config.display.focus_color(WordKind::Heading), [10, 20, 30]);
+    }
+
+    #[test]
+    fn settings_edits_are_held_to_the_loader_bounds() {
+        let mut draft = Config::default();
+        draft.speed.target_wpm = 9000;
+        draft.speed.warmup_words = 10_000;
+        draft.speed.warmup_start = 0.0;
+        draft.display.font_size = 500.0;
+        draft.display.orp_position = 1.5;
+        draft.display.brightness = 0.0;
+        let loaded: Config = draft.to_string().parse().unwrap();
+        draft.clamp_to_limits();
+
+        assert_eq!(draft.speed.target_wpm, draft.speed.max_wpm);
+        assert_eq!(draft.speed.warmup_words, SpeedConfig::MAX_WARMUP_WORDS);
+        assert_eq!(draft.speed.warmup_start, SpeedConfig::MIN_WARMUP_START);
+        assert_eq!(draft.display.font_size, DisplayConfig::MAX_FONT_SIZE);
+        assert_eq!(draft.display.orp_position, 1.0);
+        assert_eq!(draft.display.brightness, DisplayConfig::MIN_BRIGHTNESS);
+        assert_eq!(draft.to_string(), loaded.to_string());
+    }
+
+    #[test]
+    fn settings_color_edit_changes_only_that_kind() {
+        let mut draft = Config::default();
+        draft.display.focus_colors.insert(WordKind::Heading, [0, 128, 255]);
+        draft.clamp_to_limits();
+        let saved: Config = draft.to_string().parse().unwrap();
+        assert_eq!(saved.display.focus_color(WordKind::Heading), [0, 128, 255]);
+        assert_eq!(saved.display.focus_color(WordKind::Number), DisplayConfig::DEFAULT_FOCUS_COLOR);
+    }
+
+    #[test]
+    fn reader_grows_with_the_font_size() {
+        let mut display = DisplayConfig {
+            font_size: DisplayConfig::BASE_FONT_SIZE,
+            ..Default::default()
+        };
+        assert_eq!(display.font_scale(), 1.0);
+        let (base_width, base_height) = display.reader_size();
+
+        display.font_size = DisplayConfig::BASE_FONT_SIZE * 2.0;
+        assert_eq!(display.font_scale(), 2.0);
+        let (width, height) = display.reader_size();
+        assert!(width >= 620.0 * 2.0 && width > base_width);
+        assert!(height >= 90.0 * 2.0 && height >= base_height);
+    }
+
+    #[test]
+    fn speed_keys_step_plain_fine_and_coarse() {
+        let speed = SpeedConfig::default();
+        assert_eq!(speed.step(false, false), 25);
+        assert_eq!(speed.step(true, false), 5);
+        assert_eq!(speed.step(false, true), 100);
+        assert_eq!(speed.step(true, true), 100);
+
+        let config: Config = "[speed]\nadjust_step = 40\n".parse().unwrap();
+        assert_eq!(config.speed.step(false, false), 40);
+        let zero: Config = "[speed]\nadjust_step = 0\n".parse().unwrap();
+        assert_eq!(zero.speed.step(false, false), 1);
+    }
+
+    #[test]
+    fn raised_max_wpm_is_reachable() {
+        let config: Config = "[speed]\nmax_wpm = 1500\ntarget_wpm = 1400\n".parse().unwrap();
+        let speed = &config.speed;
+        let mut engine = RSVPEngine::builder("one two three")
+            .wpm_range(speed.min_wpm, speed.max_wpm)
+            .target_wpm(speed.target_wpm)
+            .build();
+        engine.adjust_speed(speed.step(false, true) as i32);
+        assert_eq!(engine.get_target_wpm(), 1500);
+        engine.adjust_speed(100);
+        assert_eq!(engine.get_target_wpm(), 1500);
+    }
+
+    #[test]
+    fn inverted_speed_bounds_are_corrected() {
+        let config: Config = "[speed]\nmin_wpm = 400\nmax_wpm = 300\ntarget_wpm = 200\n".parse().unwrap();
+        let speed = &config.speed;
+        assert!(speed.min_wpm <= speed.start_wpm());
+        assert!(speed.start_wpm() <= speed.target_wpm);
+        assert!(speed.target_wpm <= speed.max_wpm);
+        assert_eq!((speed.min_wpm, speed.max_wpm, speed.target_wpm), (400, 400, 400));
     }
 }
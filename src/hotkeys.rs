@@ -0,0 +1,184 @@
+use anyhow::{anyhow, bail, Result};
+use std::fmt;
+
+/// Key names accepted in hotkey combos besides single letters and digits
+const NAMED_KEYS: &[&str] = &[
+    "space", "return", "tab", "escape", "f1", "f2", "f3", "f4", "f5", "f6", "f7", "f8", "f9",
+    "f10", "f11", "f12",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Modifiers {
+    pub cmd: bool,
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool, // Option on macOS
+}
+
+/// A global key combination such as `["cmd", "ctrl", "r"]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hotkey {
+    pub modifiers: Modifiers,
+    pub key: String, // Lowercase key name: "r", "7", "space", "f5", ...
+}
+
+impl Hotkey {
+    /// Cmd+Ctrl+R, used when `start_reading` can't be parsed
+    pub fn default_start_reading() -> Self {
+        Self {
+            modifiers: Modifiers { cmd: true, ctrl: true, ..Modifiers::default() },
+            key: "r".to_string(),
+        }
+    }
+
+    /// Parse a combo from its config form: modifier names plus exactly one key, any order
+    pub fn parse(parts: &[String]) -> Result<Self> {
+        let mut modifiers = Modifiers::default();
+        let mut key: Option<String> = None;
+
+        for part in parts {
+            let part = part.trim().to_lowercase();
+            match part.as_str() {
+                "cmd" | "command" | "super" | "meta" => modifiers.cmd = true,
+                "ctrl" | "control" => modifiers.ctrl = true,
+                "shift" => modifiers.shift = true,
+                "alt" | "option" | "opt" => modifiers.alt = true,
+                name if is_key_name(name) => {
+                    if let Some(previous) = &key {
+                        bail!("hotkey has two keys, \"{}\" and \"{}\"", previous, name);
+                    }
+                    key = Some(name.to_string());
+                }
+                other => bail!("unknown key \"{}\" in hotkey", other),
+            }
+        }
+
+        let key = key.ok_or_else(|| anyhow!("hotkey has no key besides modifiers"))?;
+        Ok(Self { modifiers, key })
+    }
+
+    /// The combo in the accelerator form of the `global-hotkey` crate, e.g. "super+control+KeyR"
+    pub fn accelerator(&self) -> String {
+        let m = self.modifiers;
+        let mut parts: Vec<String> = [(m.cmd, "super"), (m.ctrl, "control"), (m.alt, "alt"), (m.shift, "shift")]
+            .into_iter()
+            .filter(|(held, _)| *held)
+            .map(|(_, name)| name.to_string())
+            .collect();
+        parts.push(accelerator_key(&self.key));
+        parts.join("+")
+    }
+}
+
+/// Key code name for a key name accepted by `Hotkey::parse`
+fn accelerator_key(key: &str) -> String {
+    match key {
+        "space" => "Space".to_string(),
+        "return" => "Enter".to_string(),
+        "tab" => "Tab".to_string(),
+        "escape" => "Escape".to_string(),
+        function if function.starts_with('f') && function.len() > 1 => function.to_uppercase(),
+        digit if digit.chars().all(|c| c.is_ascii_digit()) => format!("Digit{}", digit),
+        letter => format!("Key{}", letter.to_uppercase()),
+    }
+}
+
+// Carbon hotkey errors (macOS)
+const PARAM_ERR: i32 = -50;
+const EVENT_HOT_KEY_EXISTS_ERR: i32 = -9878;
+const EVENT_HOT_KEY_INVALID_ERR: i32 = -9879;
+
+/// Explain why registering a hotkey failed, from the OSStatus Carbon's
+/// `RegisterEventHotKey` returned
+pub fn describe_carbon_status(status: i32) -> String {
+    match status {
+        EVENT_HOT_KEY_EXISTS_ERR => {
+            "it is already taken, most likely by a system shortcut or another app".to_string()
+        }
+        EVENT_HOT_KEY_INVALID_ERR => "macOS doesn't accept it as a hotkey".to_string(),
+        PARAM_ERR => "it isn't a valid key combination".to_string(),
+        other => format!("registration failed (OSStatus {})", other),
+    }
+}
+
+fn is_key_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    let single = matches!((chars.next(), chars.next()), (Some(c), None) if c.is_ascii_alphanumeric());
+    single || NAMED_KEYS.contains(&name)
+}
+
+/// Human form for menus and messages, e.g. "Cmd+Ctrl+R"
+impl fmt::Display for Hotkey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let m = self.modifiers;
+        for (held, name) in [(m.cmd, "Cmd"), (m.ctrl, "Ctrl"), (m.alt, "Option"), (m.shift, "Shift")] {
+            if held {
+                write!(f, "{}+", name)?;
+            }
+        }
+        let mut chars = self.key.chars();
+        match chars.next() {
+            Some(first) => write!(f, "{}{}", first.to_ascii_uppercase(), chars.as_str()),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn combo(parts: &[&str]) -> Result<Hotkey> {
+        Hotkey::parse(&parts.iter().map(|part| part.to_string()).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn modifiers_parse_in_any_order_and_case() {
+        let hotkey = combo(&["R", "Ctrl", "command"]).unwrap();
+        assert_eq!(hotkey, Hotkey::default_start_reading());
+        assert_eq!(combo(&["option", "shift", "f5"]).unwrap().modifiers, Modifiers { alt: true, shift: true, ..Modifiers::default() });
+    }
+
+    #[test]
+    fn combos_need_exactly_one_known_key() {
+        assert!(combo(&["cmd", "ctrl"]).is_err());
+        assert!(combo(&["cmd", "r", "t"]).is_err());
+        assert!(combo(&["cmd", "hyper", "r"]).is_err());
+        assert!(combo(&["cmd", "f13"]).is_err());
+    }
+
+    #[test]
+    fn display_names_modifiers_then_key() {
+        assert_eq!(Hotkey::default_start_reading().to_string(), "Cmd+Ctrl+R");
+        assert_eq!(combo(&["shift", "alt", "space"]).unwrap().to_string(), "Option+Shift+Space");
+    }
+
+    #[test]
+    fn accelerator_names_keys_by_code() {
+        assert_eq!(Hotkey::default_start_reading().accelerator(), "super+control+KeyR");
+        assert_eq!(combo(&["alt", "shift", "7"]).unwrap().accelerator(), "alt+shift+Digit7");
+        assert_eq!(combo(&["ctrl", "return"]).unwrap().accelerator(), "control+Enter");
+        assert_eq!(combo(&["f12"]).unwrap().accelerator(), "F12");
+        assert_eq!(combo(&["cmd", "escape"]).unwrap().accelerator(), "super+Escape");
+    }
+
+    /// Every key `parse` accepts has to mean something to the backend used on Linux and Windows
+    #[cfg(not(target_os = "macos"))]
+    #[test]
+    fn every_key_is_understood_by_global_hotkey() {
+        let singles = ('a'..='z').chain('0'..='9').map(String::from);
+        for key in singles.chain(NAMED_KEYS.iter().map(|name| name.to_string())) {
+            let hotkey = combo(&["cmd", "ctrl", "alt", "shift", &key]).unwrap();
+            let accelerator = hotkey.accelerator();
+            assert!(accelerator.parse::<global_hotkey::hotkey::HotKey>().is_ok(), "{} -> {}", key, accelerator);
+        }
+    }
+
+    #[test]
+    fn carbon_statuses_explain_the_failure() {
+        assert!(describe_carbon_status(-9878).contains("already taken"));
+        assert!(describe_carbon_status(-9879).contains("doesn't accept"));
+        assert!(describe_carbon_status(-50).contains("isn't a valid key combination"));
+        assert_eq!(describe_carbon_status(-1), "registration failed (OSStatus -1)");
+    }
+}
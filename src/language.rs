@@ -0,0 +1,142 @@
+use crate::tokenizer::Tokenization;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Script {
+    Latin,
+    Cyrillic,
+    Cjk,
+    Arabic,
+}
+
+impl Script {
+    /// Script a single character belongs to, if it is one we handle
+    pub fn of_char(c: char) -> Option<Script> {
+        match c {
+            'a'..='z' | 'A'..='Z' | '\u{00C0}'..='\u{024F}' => Some(Script::Latin),
+            '\u{0400}'..='\u{052F}' => Some(Script::Cyrillic),
+            '\u{0600}'..='\u{06FF}'
+            | '\u{0750}'..='\u{077F}'
+            | '\u{08A0}'..='\u{08FF}'
+            | '\u{FB50}'..='\u{FDFF}'
+            | '\u{FE70}'..='\u{FEFF}' => Some(Script::Arabic),
+            // Kana, CJK ideographs and compatibility ideographs (Hangul is space-delimited)
+            '\u{3040}'..='\u{30FF}'
+            | '\u{3400}'..='\u{4DBF}'
+            | '\u{4E00}'..='\u{9FFF}'
+            | '\u{F900}'..='\u{FAFF}' => Some(Script::Cjk),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TextDirection {
+    Ltr,
+    Rtl,
+}
+
+impl TextDirection {
+    /// Direction a run of text is laid out in, from its dominant script
+    /// (left-to-right when it has no letters, like numbers)
+    pub fn of_text(text: &str) -> TextDirection {
+        match detect_script(text) {
+            Script::Arabic => TextDirection::Rtl,
+            _ => TextDirection::Ltr,
+        }
+    }
+}
+
+/// Dominant script of the text by letter count, Latin when nothing matches
+pub fn detect_script(text: &str) -> Script {
+    let mut counts = [0usize; 4];
+    for script in text.chars().filter_map(Script::of_char) {
+        counts[script as usize] += 1;
+    }
+
+    [Script::Latin, Script::Cyrillic, Script::Cjk, Script::Arabic]
+        .into_iter()
+        .max_by_key(|s| (counts[*s as usize], *s == Script::Latin))
+        .unwrap_or(Script::Latin)
+}
+
+/// Reading defaults applied for a detected script
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LanguageProfile {
+    pub script: Script,
+    pub tokenization: Tokenization,
+    pub direction: TextDirection,
+    pub per_character_rate: bool, // Speed is characters per minute rather than words
+}
+
+impl LanguageProfile {
+    pub fn for_script(script: Script) -> Self {
+        match script {
+            Script::Latin | Script::Cyrillic => Self {
+                script,
+                tokenization: Tokenization::Words,
+                direction: TextDirection::Ltr,
+                per_character_rate: false,
+            },
+            Script::Cjk => Self {
+                script,
+                tokenization: Tokenization::Characters,
+                direction: TextDirection::Ltr,
+                per_character_rate: true,
+            },
+            Script::Arabic => Self {
+                script,
+                tokenization: Tokenization::Words,
+                direction: TextDirection::Rtl,
+                per_character_rate: false,
+            },
+        }
+    }
+
+    pub fn detect(text: &str) -> Self {
+        Self::for_script(detect_script(text))
+    }
+}
+
+impl Default for LanguageProfile {
+    fn default() -> Self {
+        Self::for_script(Script::Latin)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn samples_map_to_their_profiles() {
+        let english = LanguageProfile::detect("The quick brown fox jumps over the lazy dog.");
+        assert_eq!(english, LanguageProfile::default());
+
+        let russian = LanguageProfile::detect("Съешь же ещё этих мягких французских булок.");
+        assert_eq!(russian.script, Script::Cyrillic);
+        assert_eq!(russian.tokenization, Tokenization::Words);
+
+        let japanese = LanguageProfile::detect("速読の練習をしましょう。");
+        assert_eq!(japanese.script, Script::Cjk);
+        assert_eq!(japanese.tokenization, Tokenization::Characters);
+        assert!(japanese.per_character_rate);
+
+        let arabic = LanguageProfile::detect("القراءة السريعة مهارة مفيدة");
+        assert_eq!(arabic.script, Script::Arabic);
+        assert_eq!(arabic.direction, TextDirection::Rtl);
+    }
+
+    #[test]
+    fn dominant_script_wins_in_mixed_text() {
+        assert_eq!(detect_script("東京 (Tokyo) は日本の首都です"), Script::Cjk);
+        assert_eq!(detect_script("Read the word مرحبا in context"), Script::Latin);
+    }
+
+    #[test]
+    fn text_without_letters_is_latin() {
+        assert_eq!(detect_script("1234 — 5678"), Script::Latin);
+        assert_eq!(detect_script(""), Script::Latin);
+        assert_eq!(TextDirection::of_text("42"), TextDirection::Ltr);
+    }
+}
@@ -1,2 +1,15 @@
+pub mod article;
+pub mod bench;
+pub mod calibration;
+pub mod clipboard_watch;
 pub mod config;
+pub mod hotkeys;
+pub mod language;
+pub mod notes;
+pub mod positions;
+pub mod preprocess;
+pub mod queue;
+pub mod recovery;
 pub mod rsvp_engine;
+pub mod stats;
+pub mod tokenizer;
@@ -4,11 +4,11 @@
 use eframe::egui;
 use clipboard::{ClipboardContext, ClipboardProvider};
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 #[cfg(target_os = "macos")]
-use tray_icon::{TrayIconBuilder, menu::{Menu, MenuItem, PredefinedMenuItem}};
+use tray_icon::{TrayIcon, TrayIconBuilder, menu::{Menu, MenuItem, PredefinedMenuItem}};
 
 #[cfg(target_os = "macos")]
 mod macos_utils {
@@ -23,6 +23,49 @@ mod macos_utils {
         }
     }
 
+    /// Let mouse events fall through our windows to whatever is beneath (click-through)
+    pub fn set_ignores_mouse_events(ignore: bool) {
+        use objc::runtime::{BOOL, NO, YES};
+        let flag: BOOL = if ignore { YES } else { NO };
+        unsafe {
+            let app: *mut Object = msg_send![class!(NSApplication), sharedApplication];
+            let windows: *mut Object = msg_send![app, windows];
+            let count: usize = msg_send![windows, count];
+            for i in 0..count {
+                let window: *mut Object = msg_send![windows, objectAtIndex: i];
+                let _: () = msg_send![window, setIgnoresMouseEvents: flag];
+            }
+        }
+    }
+
+    /// Empty the general pasteboard, every type on it included
+    pub fn clear_pasteboard() {
+        unsafe {
+            let pasteboard: *mut Object = msg_send![class!(NSPasteboard), generalPasteboard];
+            let _: isize = msg_send![pasteboard, clearContents];
+        }
+    }
+
+    /// RTF on the general pasteboard, if whatever was copied offers it
+    pub fn pasteboard_rtf() -> Option<String> {
+        unsafe {
+            let pasteboard: *mut Object = msg_send![class!(NSPasteboard), generalPasteboard];
+            let rtf_type: *mut Object = msg_send![class!(NSString), stringWithUTF8String: c"public.rtf".as_ptr()];
+            let data: *mut Object = msg_send![pasteboard, dataForType: rtf_type];
+            if data.is_null() {
+                return None;
+            }
+
+            let length: usize = msg_send![data, length];
+            let bytes: *const u8 = msg_send![data, bytes];
+            if bytes.is_null() || length == 0 {
+                return None;
+            }
+            // RTF itself is 7-bit; anything else is escaped inside it
+            Some(String::from_utf8_lossy(std::slice::from_raw_parts(bytes, length)).into_owned())
+        }
+    }
+
     /// Get the center position for a window on the screen containing the mouse cursor
     /// Returns (x, y) position for centering a window of given size
     pub fn get_centered_position_on_mouse_screen(window_width: f32, window_height: f32) -> (f32, f32) {
@@ -49,11 +92,12 @@ mod macos_utils {
                     let center_x = x + (w - window_width as f64) / 2.0;
                     let center_y = y + (h - window_height as f64) / 2.0;
 
-                    // Convert to top-left origin for egui
-                    // Get main screen height for coordinate conversion
-                    let main_screen: *mut Object = msg_send![class!(NSScreen), mainScreen];
-                    let main_frame: ((f64, f64), (f64, f64)) = msg_send![main_screen, frame];
-                    let main_height = main_frame.1.1;
+                    // Convert to top-left origin for egui. Screen coordinates are relative
+                    // to the primary screen (the first one), not `mainScreen`, which is
+                    // whichever screen has the key window
+                    let primary: *mut Object = msg_send![screens, objectAtIndex: 0usize];
+                    let primary_frame: ((f64, f64), (f64, f64)) = msg_send![primary, frame];
+                    let main_height = primary_frame.1.1;
 
                     let top_left_y = main_height - center_y - window_height as f64;
 
@@ -74,13 +118,51 @@ mod macos_utils {
             (center_x as f32, top_left_y as f32)
         }
     }
+
+    /// Whether the middle of a window at (x, y) (top-left origin, as egui places it)
+    /// lies on one of the connected screens
+    pub fn is_on_a_screen(x: f32, y: f32, window_width: f32, window_height: f32) -> bool {
+        unsafe {
+            let screens: *mut Object = msg_send![class!(NSScreen), screens];
+            let count: usize = msg_send![screens, count];
+            if count == 0 {
+                return false;
+            }
+            let primary: *mut Object = msg_send![screens, objectAtIndex: 0usize];
+            let primary_frame: ((f64, f64), (f64, f64)) = msg_send![primary, frame];
+            let main_height = primary_frame.1.1;
+
+            // Back to bottom-left origin, like the screen frames
+            let center_x = x as f64 + window_width as f64 / 2.0;
+            let center_y = main_height - (y as f64 + window_height as f64 / 2.0);
+            (0..count).any(|i| {
+                let screen: *mut Object = msg_send![screens, objectAtIndex: i];
+                let ((sx, sy), (w, h)): ((f64, f64), (f64, f64)) = msg_send![screen, frame];
+                center_x >= sx && center_x < sx + w && center_y >= sy && center_y < sy + h
+            })
+        }
+    }
+}
+
+/// A system facility for listening to the global reading hotkey
+trait HotkeyBackend {
+    /// Register the first of `candidates` the system accepts; pressing it sets `trigger`.
+    /// On failure the error explains what went wrong with each one.
+    fn register(&self, trigger: Arc<AtomicBool>, candidates: &[Hotkey]) -> Result<Hotkey, String>;
 }
 
+#[cfg(target_os = "macos")]
+use hotkey::Carbon as PlatformHotkeys;
+#[cfg(not(target_os = "macos"))]
+use global_hotkeys::GlobalHotkeys as PlatformHotkeys;
+
 #[cfg(target_os = "macos")]
 mod hotkey {
     use std::sync::atomic::{AtomicBool, Ordering};
     use std::sync::Arc;
     use std::os::raw::c_void;
+    use speeder::hotkeys::{describe_carbon_status, Hotkey, Modifiers};
+    use super::HotkeyBackend;
 
     // Carbon types and constants
     type OSStatus = i32;
@@ -129,8 +211,42 @@ mod hotkey {
     }
 
     const CMD_KEY: u32 = 1 << 8;  // cmdKey
+    const SHIFT_KEY: u32 = 1 << 9; // shiftKey
+    const OPTION_KEY: u32 = 1 << 11; // optionKey
     const CTRL_KEY: u32 = 1 << 12; // controlKey
-    const K_VK_R: u32 = 15; // Virtual key code for 'R'
+
+    /// Virtual key code (kVK_*) for a key name accepted by `Hotkey::parse`
+    fn virtual_key_code(key: &str) -> Option<u32> {
+        let code = match key {
+            "a" => 0, "s" => 1, "d" => 2, "f" => 3, "h" => 4, "g" => 5, "z" => 6, "x" => 7,
+            "c" => 8, "v" => 9, "b" => 11, "q" => 12, "w" => 13, "e" => 14, "r" => 15,
+            "y" => 16, "t" => 17, "1" => 18, "2" => 19, "3" => 20, "4" => 21, "6" => 22,
+            "5" => 23, "9" => 25, "7" => 26, "8" => 28, "0" => 29, "o" => 31, "u" => 32,
+            "i" => 34, "p" => 35, "l" => 37, "j" => 38, "k" => 40, "n" => 45, "m" => 46,
+            "return" => 36, "tab" => 48, "space" => 49, "escape" => 53,
+            "f1" => 122, "f2" => 120, "f3" => 99, "f4" => 118, "f5" => 96, "f6" => 97,
+            "f7" => 98, "f8" => 100, "f9" => 101, "f10" => 109, "f11" => 103, "f12" => 111,
+            _ => return None,
+        };
+        Some(code)
+    }
+
+    fn carbon_modifiers(modifiers: Modifiers) -> u32 {
+        let mut flags = 0;
+        if modifiers.cmd {
+            flags |= CMD_KEY;
+        }
+        if modifiers.shift {
+            flags |= SHIFT_KEY;
+        }
+        if modifiers.alt {
+            flags |= OPTION_KEY;
+        }
+        if modifiers.ctrl {
+            flags |= CTRL_KEY;
+        }
+        flags
+    }
 
     #[repr(C)]
     struct EventTypeSpec {
@@ -186,7 +302,16 @@ mod hotkey {
         0 // noErr
     }
 
-    pub fn setup_global_hotkey(trigger: Arc<AtomicBool>) -> bool {
+    /// Hotkeys through the Carbon event manager, which doesn't steal window focus
+    pub struct Carbon;
+
+    impl HotkeyBackend for Carbon {
+        fn register(&self, trigger: Arc<AtomicBool>, candidates: &[Hotkey]) -> Result<Hotkey, String> {
+            setup_global_hotkey(trigger, candidates)
+        }
+    }
+
+    fn setup_global_hotkey(trigger: Arc<AtomicBool>, candidates: &[Hotkey]) -> Result<Hotkey, String> {
         unsafe {
             TRIGGER_FLAG = Some(trigger);
 
@@ -206,199 +331,1862 @@ mod hotkey {
             );
 
             if status != 0 {
-                eprintln!("Failed to install event handler: {}", status);
-                return false;
+                return Err(format!("Failed to install hotkey event handler (OSStatus {})", status));
             }
 
-            let hot_key_id = HotKeyID {
-                signature: 0x53504452, // 'SPDR'
-                id: 1,
-            };
+            let mut problems = Vec::new();
+            for hotkey in candidates {
+                let Some(key_code) = virtual_key_code(&hotkey.key) else {
+                    problems.push(format!("{}: the key isn't supported", hotkey));
+                    continue;
+                };
+
+                let hot_key_id = HotKeyID {
+                    signature: 0x53504452, // 'SPDR'
+                    id: 1,
+                };
+
+                let mut hotkey_ref: EventHotKeyRef = std::ptr::null_mut();
+                let status = RegisterEventHotKey(
+                    key_code,
+                    carbon_modifiers(hotkey.modifiers),
+                    hot_key_id,
+                    GetEventDispatcherTarget(),
+                    0,
+                    &mut hotkey_ref,
+                );
 
-            let mut hotkey_ref: EventHotKeyRef = std::ptr::null_mut();
-            let status = RegisterEventHotKey(
-                K_VK_R,
-                CMD_KEY | CTRL_KEY,
-                hot_key_id,
-                GetEventDispatcherTarget(),
-                0,
-                &mut hotkey_ref,
-            );
+                if status == 0 {
+                    return Ok(hotkey.clone());
+                }
+                problems.push(format!("{}: {}", hotkey, describe_carbon_status(status)));
+            }
 
-            if status != 0 {
-                eprintln!("Failed to register hotkey: {}", status);
-                return false;
+            Err(format!(
+                "Couldn't register a reading hotkey ({}). Set hotkeys.start_reading_fallback to another combination.",
+                problems.join("; ")
+            ))
+        }
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+mod global_hotkeys {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use global_hotkey::hotkey::HotKey;
+    use global_hotkey::{GlobalHotKeyEvent, GlobalHotKeyManager, HotKeyState};
+    use speeder::hotkeys::Hotkey;
+    use super::HotkeyBackend;
+
+    /// Hotkeys through the `global-hotkey` crate: a key grab under X11, `RegisterHotKey` on Windows
+    pub struct GlobalHotkeys;
+
+    impl HotkeyBackend for GlobalHotkeys {
+        fn register(&self, trigger: Arc<AtomicBool>, candidates: &[Hotkey]) -> Result<Hotkey, String> {
+            // On Windows the manager must be made on the thread that runs the event loop, as here in `main`
+            let manager = GlobalHotKeyManager::new().map_err(|e| format!("Couldn't listen for hotkeys: {}", e))?;
+            GlobalHotKeyEvent::set_event_handler(Some(move |event: GlobalHotKeyEvent| {
+                if event.state() == HotKeyState::Pressed {
+                    trigger.store(true, Ordering::Relaxed);
+                }
+            }));
+
+            let mut problems = Vec::new();
+            for hotkey in candidates {
+                let Ok(combo) = hotkey.accelerator().parse::<HotKey>() else {
+                    problems.push(format!("{}: the key isn't supported", hotkey));
+                    continue;
+                };
+                match manager.register(combo) {
+                    Ok(()) => {
+                        // Dropping the manager would unregister the hotkey (and stop its X11 thread)
+                        std::mem::forget(manager);
+                        return Ok(hotkey.clone());
+                    }
+                    Err(global_hotkey::Error::AlreadyRegistered(_)) => {
+                        problems.push(format!("{}: it is already taken by another app", hotkey))
+                    }
+                    Err(e) => problems.push(format!("{}: {}", hotkey, e)),
+                }
+            }
+
+            Err(format!(
+                "Couldn't register a reading hotkey ({}). Set hotkeys.start_reading_fallback to another combination.",
+                problems.join("; ")
+            ))
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod x11_input {
+    use std::ffi::CStr;
+    use std::os::raw::{c_char, c_int, c_uint, c_ulong, c_void};
+    use std::sync::OnceLock;
+
+    // Xlib types
+    type Display = c_void;
+    type KeySym = c_ulong;
+
+    /// The Xlib and XTest calls used, loaded when first needed rather than linked, so
+    /// Speeder builds and runs without libXtst (reading only what is already copied)
+    struct XLib {
+        open_display: unsafe extern "C" fn(*const c_char) -> *mut Display,
+        string_to_keysym: unsafe extern "C" fn(*const c_char) -> KeySym,
+        keysym_to_keycode: unsafe extern "C" fn(*mut Display, KeySym) -> u8,
+        flush: unsafe extern "C" fn(*mut Display) -> c_int,
+        close_display: unsafe extern "C" fn(*mut Display) -> c_int,
+        fake_key_event: unsafe extern "C" fn(*mut Display, c_uint, c_int, c_ulong) -> c_int,
+    }
+
+    /// Look up the function `name` in the library `handle`; `F` must be its pointer type
+    unsafe fn symbol<F: Copy>(handle: *mut c_void, name: &CStr) -> Option<F> {
+        let symbol = libc::dlsym(handle, name.as_ptr());
+        (!symbol.is_null()).then(|| std::mem::transmute_copy::<*mut c_void, F>(&symbol))
+    }
+
+    unsafe fn load() -> Option<XLib> {
+        let x11 = libc::dlopen(c"libX11.so.6".as_ptr(), libc::RTLD_NOW);
+        let xtst = libc::dlopen(c"libXtst.so.6".as_ptr(), libc::RTLD_NOW);
+        if x11.is_null() || xtst.is_null() {
+            return None;
+        }
+        // The handles stay open for the life of the process
+        Some(XLib {
+            open_display: symbol(x11, c"XOpenDisplay")?,
+            string_to_keysym: symbol(x11, c"XStringToKeysym")?,
+            keysym_to_keycode: symbol(x11, c"XKeysymToKeycode")?,
+            flush: symbol(x11, c"XFlush")?,
+            close_display: symbol(x11, c"XCloseDisplay")?,
+            fake_key_event: symbol(xtst, c"XTestFakeKeyEvent")?,
+        })
+    }
+
+    fn xlib() -> Option<&'static XLib> {
+        static XLIB: OnceLock<Option<XLib>> = OnceLock::new();
+        XLIB.get_or_init(|| {
+            let xlib = unsafe { load() };
+            if xlib.is_none() {
+                log::warn!("libX11/libXtst not found; select-and-read falls back to the clipboard");
+            }
+            xlib
+        })
+        .as_ref()
+    }
+
+    /// Simulate Ctrl+C to copy currently selected text. Does nothing without libXtst,
+    /// leaving the clipboard as it is.
+    pub fn simulate_copy() {
+        let Some(x) = xlib() else {
+            return;
+        };
+        unsafe {
+            let display = (x.open_display)(std::ptr::null());
+            if display.is_null() {
+                return;
+            }
+            let control = (x.keysym_to_keycode)(display, (x.string_to_keysym)(c"Control_L".as_ptr())) as c_uint;
+            let c = (x.keysym_to_keycode)(display, (x.string_to_keysym)(c"c".as_ptr())) as c_uint;
+            if control != 0 && c != 0 {
+                (x.fake_key_event)(display, control, 1, 0);
+                (x.fake_key_event)(display, c, 1, 0);
+                (x.fake_key_event)(display, c, 0, 0);
+                (x.fake_key_event)(display, control, 0, 0);
+                (x.flush)(display);
+            }
+            (x.close_display)(display);
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod windows_input {
+    // Win32 types and constants
+    #[repr(C)]
+    struct KeyboardInput {
+        virtual_key: u16,
+        scan: u16,
+        flags: u32,
+        time: u32,
+        extra_info: usize,
+    }
+
+    #[repr(C)]
+    struct Input {
+        kind: u32,
+        keyboard: KeyboardInput,
+        _pad: [u8; 8], // INPUT is a union; its mouse variant is 8 bytes longer
+    }
+
+    const INPUT_KEYBOARD: u32 = 1;
+    const KEYEVENTF_KEYUP: u32 = 0x0002;
+    const VK_CONTROL: u16 = 0x11;
+    const VK_C: u16 = 0x43;
+
+    #[link(name = "user32")]
+    extern "system" {
+        fn SendInput(count: u32, inputs: *const Input, size: i32) -> u32;
+    }
+
+    /// Simulate Ctrl+C to copy currently selected text
+    pub fn simulate_copy() {
+        let key = |virtual_key, flags| Input {
+            kind: INPUT_KEYBOARD,
+            keyboard: KeyboardInput { virtual_key, scan: 0, flags, time: 0, extra_info: 0 },
+            _pad: [0; 8],
+        };
+        let inputs = [
+            key(VK_CONTROL, 0),
+            key(VK_C, 0),
+            key(VK_C, KEYEVENTF_KEYUP),
+            key(VK_CONTROL, KEYEVENTF_KEYUP),
+        ];
+        unsafe {
+            SendInput(inputs.len() as u32, inputs.as_ptr(), std::mem::size_of::<Input>() as i32);
+        }
+    }
+}
+
+#[cfg(all(target_os = "macos", feature = "accessibility"))]
+mod accessibility {
+    use std::ffi::CString;
+    use std::os::raw::{c_char, c_void};
+
+    // CoreFoundation / Accessibility types
+    type CFTypeRef = *mut c_void;
+    type CFStringRef = *mut c_void;
+    type CFIndex = isize;
+    type AXUIElementRef = *mut c_void;
+    type AXError = i32;
+
+    const K_AX_ERROR_SUCCESS: AXError = 0;
+    const K_CF_STRING_ENCODING_UTF8: u32 = 0x0800_0100;
+
+    #[link(name = "ApplicationServices", kind = "framework")]
+    extern "C" {
+        fn AXIsProcessTrusted() -> bool;
+        fn AXUIElementCreateSystemWide() -> AXUIElementRef;
+        fn AXUIElementCopyAttributeValue(
+            element: AXUIElementRef,
+            attribute: CFStringRef,
+            value: *mut CFTypeRef,
+        ) -> AXError;
+    }
+
+    #[link(name = "CoreFoundation", kind = "framework")]
+    extern "C" {
+        fn CFStringCreateWithCString(alloc: *const c_void, c_str: *const c_char, encoding: u32) -> CFStringRef;
+        fn CFStringGetLength(string: CFStringRef) -> CFIndex;
+        fn CFStringGetMaximumSizeForEncoding(length: CFIndex, encoding: u32) -> CFIndex;
+        fn CFStringGetCString(string: CFStringRef, buffer: *mut c_char, size: CFIndex, encoding: u32) -> bool;
+        fn CFGetTypeID(cf: CFTypeRef) -> usize;
+        fn CFStringGetTypeID() -> usize;
+        fn CFRelease(cf: CFTypeRef);
+    }
+
+    /// Whether the user has granted Speeder Accessibility access
+    pub fn is_trusted() -> bool {
+        unsafe { AXIsProcessTrusted() }
+    }
+
+    /// Selected text of the focused UI element in any app, read without the clipboard.
+    /// Returns None when permission is missing or the element exposes no selection.
+    pub fn selected_text() -> Option<String> {
+        if !is_trusted() {
+            return None;
+        }
+
+        unsafe {
+            let system = AXUIElementCreateSystemWide();
+            if system.is_null() {
+                return None;
             }
+            let focused = copy_attribute(system, "AXFocusedUIElement");
+            CFRelease(system);
+
+            let focused = focused?;
+            let selected = copy_attribute(focused, "AXSelectedText");
+            CFRelease(focused);
+
+            let selected = selected?;
+            let text = cfstring_to_string(selected);
+            CFRelease(selected);
+
+            text.filter(|t| !t.trim().is_empty())
+        }
+    }
+
+    /// Copy an attribute value; the caller owns (and must release) the result
+    unsafe fn copy_attribute(element: AXUIElementRef, name: &str) -> Option<CFTypeRef> {
+        let name = CString::new(name).ok()?;
+        let attribute = CFStringCreateWithCString(std::ptr::null(), name.as_ptr(), K_CF_STRING_ENCODING_UTF8);
+        if attribute.is_null() {
+            return None;
+        }
+
+        let mut value: CFTypeRef = std::ptr::null_mut();
+        let status = AXUIElementCopyAttributeValue(element, attribute, &mut value);
+        CFRelease(attribute);
+
+        if status == K_AX_ERROR_SUCCESS && !value.is_null() {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    unsafe fn cfstring_to_string(value: CFTypeRef) -> Option<String> {
+        if CFGetTypeID(value) != CFStringGetTypeID() {
+            return None;
+        }
+
+        let length = CFStringGetLength(value);
+        let size = CFStringGetMaximumSizeForEncoding(length, K_CF_STRING_ENCODING_UTF8) + 1;
+        let mut buffer = vec![0u8; size as usize];
+        if !CFStringGetCString(value, buffer.as_mut_ptr() as *mut c_char, size, K_CF_STRING_ENCODING_UTF8) {
+            return None;
+        }
+
+        let end = buffer.iter().position(|&b| b == 0).unwrap_or(buffer.len());
+        buffer.truncate(end);
+        String::from_utf8(buffer).ok()
+    }
+}
+
+use speeder::article;
+use speeder::bench;
+use speeder::calibration::{self, CalibrationRun};
+use speeder::clipboard_watch::ClipboardWatcher;
+use speeder::hotkeys::Hotkey;
+use speeder::config::{Config, DisplayConfig, FocusLossAction, OrpMode, SpeedConfig, FocusTrainerConfig, HotkeyConfig, PositionStyle, ProgressBasis, Theme};
+use speeder::language::{LanguageProfile, TextDirection};
+use speeder::notes::{self, Notes};
+use speeder::positions::ReadingPositions;
+use speeder::preprocess;
+use speeder::queue::ReadingQueue;
+use speeder::recovery;
+use speeder::rsvp_engine::{EngineState, RSVPEngine, RSVPEngineBuilder, Word, WordKind};
+use speeder::stats::{self, ReadingStats};
+use speeder::tokenizer::{self, Tokenization};
+
+/// How often the crash recovery snapshot is rewritten while reading
+const RECOVERY_SAVE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Shown when the hotkey finds neither a selection nor clipboard text
+const NOTHING_TO_READ: &str = "Nothing to read — copy some text first";
+
+/// Extra height above and below the progress bar that still picks it up for scrubbing
+const PROGRESS_HIT_MARGIN: f32 = 6.0;
+
+/// Trackpad scrolling (in points) that seeks one word
+const SCROLL_POINTS_PER_WORD: f32 = 24.0;
+/// Words a page of mouse-wheel scrolling seeks
+const SCROLL_PAGE_WORDS: f32 = 20.0;
+
+/// Size of the `B` review line relative to the word
+const REVIEW_FONT_SCALE: f32 = 0.45;
+
+/// Repaint interval while paused or waiting for a rating, when nothing moves on its own
+const IDLE_REPAINT_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How long a hint such as `NOTHING_TO_READ` stays up
+const IDLE_MESSAGE_DURATION: Duration = Duration::from_millis(1500);
+
+/// Shown while the `--url` article downloads
+const LOADING_ARTICLE: &str = "Loading article…";
+
+/// How long the reason a `--url` article couldn't be read stays up
+const FETCH_ERROR_DURATION: Duration = Duration::from_secs(5);
+
+/// Side of the square drag area in the bottom-right corner used to resize the window
+const RESIZE_HANDLE_SIZE: f32 = 14.0;
+
+/// How far left of the pin the focus glyph starts. With optical centering the
+/// glyph's middle rather than its left edge sits on the pin, which keeps the
+/// perceived fixation point still when glyph widths vary.
+fn optical_nudge(advance: f32, optical_center: bool) -> f32 {
+    if optical_center {
+        advance / 2.0
+    } else {
+        0.0
+    }
+}
+
+/// Starting size of a sentence shown while paused, relative to the word font
+const PEEK_FONT_SCALE: f32 = 0.6;
+
+/// Most words of a sentence shown while paused
+const PEEK_MAX_WORDS: usize = 60;
 
-            true
+/// Opacity of context words relative to the word's own letters
+const CONTEXT_ALPHA: f32 = 0.45;
+
+/// Word of a `total`-word text at `x` along the progress bar `track`: the first at its
+/// left edge, the last at its right, and in proportion in between
+fn track_index(x: f32, track: egui::Rect, total: usize) -> usize {
+    let fraction = ((x - track.left()) / track.width().max(1.0)).clamp(0.0, 1.0);
+    ((fraction * total as f32).round() as usize).min(total.saturating_sub(1))
+}
+
+/// Words around the current one, each with its opacity
+struct ContextWords {
+    preceding: Vec<(String, f32)>,
+    following: Vec<(String, f32)>,
+}
+
+/// Indices of up to `n` words before and after word `index` of `total`
+fn context_bounds(index: usize, total: usize, n: usize) -> (std::ops::Range<usize>, std::ops::Range<usize>) {
+    let index = index.min(total);
+    let after_start = (index + 1).min(total);
+    (index.saturating_sub(n)..index, after_start..(after_start + n).min(total))
+}
+
+/// Opacity of a read word `distance` words behind the current one. With fading, the
+/// farthest of `n` context words is faintest, so there's little to look back at.
+fn read_context_alpha(distance: usize, n: usize, fade: bool) -> f32 {
+    if !fade {
+        return CONTEXT_ALPHA;
+    }
+    CONTEXT_ALPHA * (1.0 - distance as f32 / (n + 1) as f32).max(0.0)
+}
+
+fn context_words(words: &[Word], index: usize, n: usize, fade: bool) -> ContextWords {
+    let (before, after) = context_bounds(index, words.len(), n);
+    let preceding = before
+        .map(|i| (words[i].text.clone(), read_context_alpha(index - i, n, fade)))
+        .collect();
+    let following = after.map(|i| (words[i].text.clone(), CONTEXT_ALPHA)).collect();
+    ContextWords { preceding, following }
+}
+
+/// Scale a color toward black (brightness < 1) or white (brightness > 1), keeping alpha
+fn apply_brightness(color: egui::Color32, brightness: f32) -> egui::Color32 {
+    let b = brightness.clamp(DisplayConfig::MIN_BRIGHTNESS, DisplayConfig::MAX_BRIGHTNESS);
+    let scale = |c: u8| -> u8 {
+        let c = c as f32;
+        let v = if b < 1.0 { c * b } else { c + (255.0 - c) * (b - 1.0) };
+        v.round().clamp(0.0, 255.0) as u8
+    };
+    let [r, g, blue, alpha] = color.to_srgba_unmultiplied();
+    egui::Color32::from_rgba_unmultiplied(scale(r), scale(g), scale(blue), alpha)
+}
+
+/// Color for the pause cue: `calm` for a plain word, shading to `warm` as the
+/// upcoming dwell factor approaches a full sentence-end pause
+fn pause_cue_color(factor: f32, calm: egui::Color32, warm: egui::Color32) -> egui::Color32 {
+    const FULL_PAUSE: f32 = 1.6; // Roughly a sentence-ending word of average length
+    let t = ((factor - 1.0) / (FULL_PAUSE - 1.0)).clamp(0.0, 1.0);
+    calm.lerp_to_gamma(warm, t)
+}
+
+/// A word's parts in screen order. Right-to-left words read from the right, so
+/// their segments are mirrored around the focus.
+fn screen_parts(word: &Word) -> Option<(String, char, String)> {
+    let (before, focus, after) = word.get_parts()?;
+    Some(match word.direction {
+        TextDirection::Ltr => (before, focus, after),
+        TextDirection::Rtl => (after.chars().rev().collect(), focus, before.chars().rev().collect()),
+    })
+}
+
+/// HUD line such as "142/980 · 420wpm · 38% · 2:10 left"
+fn hud_text(position: &str, rate: u32, rate_unit: &str, progress: f32, time_left: Option<Duration>) -> String {
+    let percent = (progress.clamp(0.0, 1.0) * 100.0).round() as u32;
+    let mut text = format!("{} · {}{} · {}%", position, rate, rate_unit, percent);
+    if let Some(time_left) = time_left {
+        text.push_str(" · ");
+        text.push_str(&time_left_text(time_left));
+    }
+    text
+}
+
+/// Expected reading time still to go, e.g. "2:10 left"
+fn time_left_text(remaining: Duration) -> String {
+    let secs = remaining.as_secs();
+    format!("{}:{:02} left", secs / 60, secs % 60)
+}
+
+/// Position part of the HUD: "142/980", or what is left ("838 left", or "2:10 left"
+/// when progress is measured by time)
+fn position_text(
+    style: PositionStyle,
+    basis: ProgressBasis,
+    position: usize,
+    total: usize,
+    remaining: Duration,
+) -> String {
+    match (style, basis) {
+        (PositionStyle::Elapsed, _) => format!("{}/{}", position, total),
+        (PositionStyle::Remaining, ProgressBasis::Words) => format!("{} left", total.saturating_sub(position)),
+        (PositionStyle::Remaining, ProgressBasis::Time) => time_left_text(remaining),
+    }
+}
+
+/// How full the progress bar is, measured by `basis`
+fn bar_progress(engine: &RSVPEngine, basis: ProgressBasis) -> f32 {
+    match basis {
+        ProgressBasis::Words => engine.get_progress(),
+        ProgressBasis::Time => engine.time_progress(),
+    }
+}
+
+/// Schedules the focus trainer's dimmed periods from a seeded generator
+struct FocusTrainer {
+    rng_state: u64,
+    interval: Duration,
+    duration: Duration,
+    next_start: std::time::Instant,
+}
+
+impl FocusTrainer {
+    /// Context alpha while dimmed
+    const DIM_ALPHA: f32 = 0.08;
+
+    fn new(config: &FocusTrainerConfig, now: std::time::Instant) -> Self {
+        let mut trainer = Self {
+            rng_state: config.seed,
+            interval: Duration::from_millis(config.interval_ms.max(1)),
+            duration: Duration::from_millis(config.duration_ms),
+            next_start: now,
+        };
+        trainer.schedule_after(now);
+        trainer
+    }
+
+    /// splitmix64, mapped to [0, 1)
+    fn next_random(&mut self) -> f32 {
+        self.rng_state = self.rng_state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.rng_state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        (z >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// Pick the next dimmed period, 0.5x–1.5x the configured interval away
+    fn schedule_after(&mut self, from: std::time::Instant) {
+        let gap = self.interval.mul_f32(0.5 + self.next_random());
+        self.next_start = from + gap;
+    }
+
+    /// Whether the context is dimmed at `now`, rolling the schedule past finished periods
+    fn is_dimmed(&mut self, now: std::time::Instant) -> bool {
+        while now >= self.next_start + self.duration {
+            let end = self.next_start + self.duration;
+            self.schedule_after(end);
+        }
+        now >= self.next_start
+    }
+
+    /// When the context next dims or brightens
+    fn next_change(&self, now: std::time::Instant) -> std::time::Instant {
+        if now < self.next_start {
+            self.next_start
+        } else {
+            self.next_start + self.duration
+        }
+    }
+
+    fn context_alpha(&mut self, now: std::time::Instant) -> f32 {
+        if self.is_dimmed(now) {
+            Self::DIM_ALPHA
+        } else {
+            1.0
+        }
+    }
+}
+
+/// Where the blank lead-in before the first word stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LeadIn {
+    Holding,   // Still blank; the engine waits
+    JustEnded, // Ran out since the last check; the first word starts now
+    Over,      // No lead-in, or it ended earlier
+}
+
+impl LeadIn {
+    /// Check the lead-in deadline at `now`, clearing it once it has passed
+    fn check(until: &mut Option<std::time::Instant>, now: std::time::Instant) -> Self {
+        match *until {
+            Some(deadline) if now < deadline => LeadIn::Holding,
+            Some(_) => {
+                *until = None;
+                LeadIn::JustEnded
+            }
+            None => LeadIn::Over,
+        }
+    }
+}
+
+/// Absolute seek destinations (Home / End)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SeekTarget {
+    Start,
+    End,
+}
+
+impl SeekTarget {
+    /// Word index for a text of `total_words` words (0 when empty)
+    fn index(self, total_words: usize) -> usize {
+        match self {
+            SeekTarget::Start => 0,
+            SeekTarget::End => total_words.saturating_sub(1),
+        }
+    }
+}
+
+/// Actions the arrow keys can be mapped to in the reading window
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KeyAction {
+    SpeedUp,
+    SpeedDown,
+    SeekBack,
+    SeekForward,
+}
+
+impl KeyAction {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "speed_up" => Some(KeyAction::SpeedUp),
+            "speed_down" => Some(KeyAction::SpeedDown),
+            "seek_back" => Some(KeyAction::SeekBack),
+            "seek_forward" => Some(KeyAction::SeekForward),
+            _ => None,
+        }
+    }
+}
+
+/// Arrow key → action mapping resolved from `HotkeyConfig`
+struct ArrowKeyMap {
+    up: KeyAction,
+    down: KeyAction,
+    left: KeyAction,
+    right: KeyAction,
+}
+
+impl ArrowKeyMap {
+    fn from_config(hotkeys: &HotkeyConfig) -> Self {
+        // Unknown action names fall back to the stock binding for that key
+        let resolve = |key: &str, name: &str, fallback: KeyAction| {
+            KeyAction::from_name(name).unwrap_or_else(|| {
+                log::warn!("Unknown action '{}' for {}, using default", name, key);
+                fallback
+            })
+        };
+
+        Self {
+            up: resolve("arrow_up", &hotkeys.arrow_up, KeyAction::SpeedUp),
+            down: resolve("arrow_down", &hotkeys.arrow_down, KeyAction::SpeedDown),
+            left: resolve("arrow_left", &hotkeys.arrow_left, KeyAction::SeekBack),
+            right: resolve("arrow_right", &hotkeys.arrow_right, KeyAction::SeekForward),
+        }
+    }
+
+    fn action(&self, key: egui::Key) -> Option<KeyAction> {
+        match key {
+            egui::Key::ArrowUp => Some(self.up),
+            egui::Key::ArrowDown => Some(self.down),
+            egui::Key::ArrowLeft => Some(self.left),
+            egui::Key::ArrowRight => Some(self.right),
+            _ => None,
+        }
+    }
+}
+
+/// Command-line options
+#[derive(Debug, Default)]
+struct LaunchOptions {
+    read_now: bool,             // --now: read the clipboard right away
+    duration: Option<Duration>, // --duration 5m: fit every read into this long
+    bench_tokenize: Option<std::path::PathBuf>, // --bench-tokenize FILE: time the text pipeline and exit
+    read_file: Option<std::path::PathBuf>, // --file FILE, or "-" for stdin: read that text right away
+    read_url: Option<String>, // --url URL: download that web article and read it
+}
+
+impl LaunchOptions {
+    fn parse(mut args: impl Iterator<Item = String>) -> Self {
+        let mut options = Self::default();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--now" => options.read_now = true,
+                "--duration" => match args.next().as_deref().and_then(parse_duration) {
+                    Some(duration) => options.duration = Some(duration),
+                    None => eprintln!("--duration needs a time such as 300, 90s or 5m"),
+                },
+                "--bench-tokenize" => match args.next() {
+                    Some(path) => options.bench_tokenize = Some(path.into()),
+                    None => eprintln!("--bench-tokenize needs a file to read"),
+                },
+                "--file" => match args.next() {
+                    Some(path) => options.read_file = Some(path.into()),
+                    None => eprintln!("--file needs a file to read"),
+                },
+                "--url" => match args.next() {
+                    Some(url) => options.read_url = Some(url),
+                    None => eprintln!("--url needs a web address to read"),
+                },
+                "-" => options.read_file = Some("-".into()),
+                other => eprintln!("Ignoring unknown argument: {}", other),
+            }
         }
+        options
     }
 }
 
-mod config;
-mod rsvp_engine;
+/// Whether to read the clipboard as soon as the app opens, before any hotkey press
+fn launch_trigger(config: &Config, launch: &LaunchOptions) -> bool {
+    config.read_on_launch || launch.read_now
+}
+
+/// Take a pending read request, so each request starts at most one read
+fn take_trigger(flag: &AtomicBool) -> bool {
+    flag.swap(false, Ordering::Relaxed)
+}
+
+/// Text for `--file`: the file's contents, or all of stdin for "-"
+fn read_launch_text(path: &std::path::Path) -> std::io::Result<String> {
+    if path.as_os_str() == "-" {
+        let mut text = String::new();
+        std::io::Read::read_to_string(&mut std::io::stdin(), &mut text)?;
+        Ok(text)
+    } else {
+        std::fs::read_to_string(path)
+    }
+}
 
-use config::Config;
-use rsvp_engine::RSVPEngine;
+/// Runs of the text pipeline timed by `--bench-tokenize`
+const BENCH_RUNS: usize = 20;
+
+/// Developer tool: time preprocessing and tokenization over a file and print the results
+fn run_tokenize_bench(path: &std::path::Path, config: &Config) -> Result<(), Box<dyn std::error::Error>> {
+    let text = std::fs::read_to_string(path)?;
+    let report = bench::tokenize_throughput(&text, config, BENCH_RUNS)?;
+    println!("{}", report);
+    Ok(())
+}
+
+/// Parse a duration like "300" (seconds), "90s", "5m" or "1h"
+fn parse_duration(s: &str) -> Option<Duration> {
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let n: u64 = number.parse().ok()?;
+    let secs = match unit {
+        "" | "s" => n,
+        "m" => n.checked_mul(60)?,
+        "h" => n.checked_mul(3600)?,
+        _ => return None,
+    };
+    Some(Duration::from_secs(secs))
+}
+
+/// Requests from the tray menu
+#[derive(Clone, Default)]
+struct MenuFlags {
+    queue_add: Arc<AtomicBool>,  // Queue the clipboard text
+    queue_read: Arc<AtomicBool>, // Read through the queue
+    calibrate: Arc<AtomicBool>,  // Read the calibration passage
+    settings: Arc<AtomicBool>,   // Open the settings window
+    quit: Arc<AtomicBool>,       // Finish the current read properly, then exit
+}
+
+/// Progress through a calibration read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Calibration {
+    Reading,             // Calibration passage on screen
+    Rating { wpm: u32 }, // Passage finished at `wpm`, waiting for a 1-5 rating
+}
+
+/// What the reader window is doing; `update` draws and moves between these
+#[derive(Clone, Debug, PartialEq)]
+enum AppState {
+    Idle,                                 // Hidden, waiting for the hotkey
+    Reading,                              // A text is on screen (playing or paused)
+    Backgrounded,                         // Paused and hidden by focus loss (`on_focus_loss = "pause"`) until brought back
+    Message(String, std::time::Instant), // Hint shown instead of a reader, until then
+}
+
+impl AppState {
+    /// Whether a text is loaded, on screen or hidden in the background
+    fn is_reading(&self) -> bool {
+        matches!(self, AppState::Reading | AppState::Backgrounded)
+    }
+}
 
 struct SpeedReaderApp {
     engine: Option<RSVPEngine>,
     config: Config,
     trigger_flag: Arc<AtomicBool>,
-    reading_active: bool,
+    copy_trigger_flag: Arc<AtomicBool>, // Set by the clipboard monitor in auto-read mode
+    clipboard_watcher: Arc<Mutex<ClipboardWatcher>>,
+    menu_flags: MenuFlags,
+    state: AppState,
     paused: bool,
     window_visible: bool,
     had_focus: bool, // Track if window ever gained focus (to detect focus *loss*)
+    was_focused: bool, // Focus state last frame (to detect focus *gain*)
     last_word: Option<(String, char, String)>,
     progress_visible_until: Option<std::time::Instant>,
-    // Remember position for same text
-    last_text: Option<String>,
-    last_position: usize,
+    basis_label_until: Option<std::time::Instant>, // Name the progress basis after toggling it
+    hud_visible_until: Option<std::time::Instant>, // HUD shows (even when off) until then, after a preset
+    lead_in_until: Option<std::time::Instant>, // Card stays blank until then so the eyes can settle
+    pending_recovery: Option<EngineState>, // Unfinished read from a crashed session, reopened at startup
+    launch_text: Option<String>, // From `--file` or stdin, read as soon as the window is up
+    article_fetch: Option<std::sync::mpsc::Receiver<Result<String, String>>>, // `--url` download under way
+    recovery_saved_at: Option<std::time::Instant>,
+    last_text: Option<String>, // Text being (or last) read, after preprocessing
+    positions: ReadingPositions, // Where reading stopped in recently read texts
+    profile: LanguageProfile, // Reading defaults for the current text's script
+    arrow_keys: ArrowKeyMap,
+    focus_trainer: Option<FocusTrainer>,
+    skimming: bool,
+    reading_queue: bool, // Current text came from the queue; finishing moves on to the next item
+    calibration: Option<Calibration>,
+    calibration_suggestion: Option<u32>, // Best speed from the calibration log, shown when rating
+    fit_duration: Option<Duration>, // From --duration: every read is paced to take this long
+    click_through_applied: bool,    // Whether the window currently lets clicks through
+    reading_stats: ReadingStats,
+    read_since: Option<std::time::Instant>, // Start of reading time not yet added to the stats
+    settings_open: bool,            // Settings window is shown; edits apply to `config` and save at once
+    notes: Notes,
+    note_draft: Option<String>, // Note being typed for the current word (keys go to the text field)
+    note_banner: Option<(String, std::time::Instant)>, // Note jumped to with `L`, shown until then
+    scroll_words: f32, // Scrolling not yet enough for a whole word of seeking
+    #[cfg(target_os = "macos")]
+    tray_icon: Option<TrayIcon>, // Its tooltip shows progress toward the daily goal
+    #[cfg(target_os = "macos")]
+    tray_totals: Option<MenuItem>, // Menu line with the total words read
+}
+
+/// Empty the clipboard
+fn clear_clipboard() {
+    #[cfg(target_os = "macos")]
+    macos_utils::clear_pasteboard();
+    // Elsewhere the clipboard crate can only replace the contents, so with nothing
+    #[cfg(not(target_os = "macos"))]
+    if let Err(e) = ClipboardContext::new().and_then(|mut ctx| ctx.set_contents(String::new())) {
+        log::warn!("Failed to clear the clipboard: {}", e);
+    }
+}
+
+/// Whether the middle of a window at (x, y) lies on one of the connected screens
+#[cfg(target_os = "macos")]
+fn is_on_screen(_ctx: &egui::Context, x: f32, y: f32, width: f32, height: f32) -> bool {
+    macos_utils::is_on_a_screen(x, y, width, height)
+}
+
+/// Whether the middle of a window at (x, y) lies on the current monitor
+#[cfg(not(target_os = "macos"))]
+fn is_on_screen(ctx: &egui::Context, x: f32, y: f32, width: f32, height: f32) -> bool {
+    ctx.input(|i| i.viewport().monitor_size).is_some_and(|monitor| {
+        let center = egui::pos2(x + width / 2.0, y + height / 2.0);
+        egui::Rect::from_min_size(egui::Pos2::ZERO, monitor).contains(center)
+    })
+}
+
+/// Sample word drawn the way the reader would show it with `config`
+fn settings_preview(ui: &mut egui::Ui, config: &Config) {
+    let word = Word::new("preview".to_string());
+    let Some((before, focus, after)) = word.get_parts() else {
+        return;
+    };
+    let palette = config.display.palette();
+    let rgb = |[r, g, b]: [u8; 3]| apply_brightness(egui::Color32::from_rgb(r, g, b), config.display.brightness);
+    let text_color = rgb(palette.text);
+    let focus_color = rgb(config.display.focus_color(WordKind::Normal));
+    let size = config.display.font_size;
+
+    egui::Frame::none()
+        .fill(rgb(palette.background))
+        .rounding(egui::Rounding::same(12.0))
+        .inner_margin(egui::Margin::same(12.0))
+        .show(ui, |ui| {
+            ui.set_width(ui.available_width());
+            ui.vertical_centered(|ui| {
+                ui.horizontal(|ui| {
+                    ui.spacing_mut().item_spacing.x = 0.0;
+                    ui.label(egui::RichText::new(before).size(size).color(text_color).monospace());
+                    ui.label(egui::RichText::new(focus.to_string()).size(size).color(focus_color).monospace().strong());
+                    ui.label(egui::RichText::new(after).size(size).color(text_color).monospace());
+                });
+            });
+        });
+}
+
+/// Menubar tooltip: progress toward the daily goal, when one is set
+#[cfg(target_os = "macos")]
+fn tray_tooltip(reading_stats: &ReadingStats, daily_goal: u64) -> String {
+    if daily_goal == 0 {
+        return "Speeder - Speed Reader".to_string();
+    }
+    stats::goal_text(reading_stats.words_on(&stats::today()), daily_goal)
 }
 
 impl SpeedReaderApp {
-    fn new(trigger_flag: Arc<AtomicBool>, config: Config) -> Self {
+    fn new(
+        trigger_flag: Arc<AtomicBool>,
+        copy_trigger_flag: Arc<AtomicBool>,
+        clipboard_watcher: Arc<Mutex<ClipboardWatcher>>,
+        menu_flags: MenuFlags,
+        fit_duration: Option<Duration>,
+        reading_stats: ReadingStats,
+        config: Config,
+    ) -> Self {
+        let pending_recovery = if config.behavior.crash_recovery {
+            recovery::load().unwrap_or_else(|e| {
+                log::warn!("Failed to load recovery file: {}", e);
+                None
+            })
+        } else {
+            None
+        };
+
         Self {
+            arrow_keys: ArrowKeyMap::from_config(&config.hotkeys),
             engine: None,
             config,
             trigger_flag,
-            reading_active: false,
+            copy_trigger_flag,
+            clipboard_watcher,
+            menu_flags,
+            state: AppState::Idle,
             paused: false,
             window_visible: true,
             had_focus: false,
+            was_focused: false,
             last_word: None,
             progress_visible_until: None,
+            basis_label_until: None,
+            hud_visible_until: None,
+            lead_in_until: None,
+            pending_recovery,
+            launch_text: None,
+            article_fetch: None,
+            recovery_saved_at: None,
             last_text: None,
-            last_position: 0,
+            positions: ReadingPositions::load().unwrap_or_else(|e| {
+                log::warn!("Failed to load reading positions: {}", e);
+                ReadingPositions::default()
+            }),
+            profile: LanguageProfile::default(),
+            focus_trainer: None,
+            skimming: false,
+            reading_queue: false,
+            calibration: None,
+            calibration_suggestion: None,
+            fit_duration,
+            click_through_applied: false,
+            reading_stats,
+            read_since: None,
+            settings_open: false,
+            notes: Notes::load().unwrap_or_else(|e| {
+                log::warn!("Failed to load notes: {}", e);
+                Notes::default()
+            }),
+            note_draft: None,
+            note_banner: None,
+            scroll_words: 0.0,
+            #[cfg(target_os = "macos")]
+            tray_icon: None,
+            #[cfg(target_os = "macos")]
+            tray_totals: None,
         }
     }
 
     fn start_reading(&mut self, _ctx: &egui::Context) {
+        match self.read_selection().filter(|text| !text.trim().is_empty()) {
+            Some(text) => self.begin_reading(text),
+            None => self.show_idle_message(NOTHING_TO_READ),
+        }
+    }
+
+    /// Start reading straight from the clipboard (auto-read on copy)
+    fn start_reading_clipboard(&mut self) {
+        match self.read_clipboard().filter(|text| !text.trim().is_empty()) {
+            Some(text) => self.begin_reading(text),
+            None => self.show_idle_message(NOTHING_TO_READ),
+        }
+    }
+
+    /// Bring up the reader window, if it's hidden: where it was last with
+    /// `behavior.remember_position`, otherwise centered on the screen with the mouse
+    fn show_window(&mut self, ctx: &egui::Context) {
+        if self.window_visible {
+            return;
+        }
+        // Open at the configured size (which can change while hidden, e.g. from the
+        // settings window)
+        let (width, height) = self.config.display.reader_size();
+        ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(egui::vec2(width, height)));
+        let position = self.remembered_position(ctx, width, height);
+        #[cfg(target_os = "macos")]
+        let position = position.or_else(|| {
+            let (x, y) = macos_utils::get_centered_position_on_mouse_screen(width, height);
+            Some(egui::pos2(x, y))
+        });
+        if let Some(position) = position {
+            ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(position));
+        }
+        ctx.send_viewport_cmd(egui::ViewportCommand::Visible(true));
+        ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+        self.window_visible = true;
+    }
+
+    /// Where the reader was when reading last stopped, if it should reopen there and
+    /// would still be on a screen (one may have been unplugged since)
+    fn remembered_position(&self, ctx: &egui::Context, width: f32, height: f32) -> Option<egui::Pos2> {
+        if !self.config.behavior.remember_position {
+            return None;
+        }
+        let (x, y) = self.config.behavior.window_position?;
+        is_on_screen(ctx, x, y, width, height).then(|| egui::pos2(x, y))
+    }
+
+    /// Remember where the reader is, for `behavior.remember_position`
+    fn save_window_position(&mut self, ctx: &egui::Context) {
+        if !self.config.behavior.remember_position || !self.window_visible {
+            return;
+        }
+        let Some(rect) = ctx.input(|i| i.viewport().outer_rect) else {
+            return;
+        };
+        let position = Some((rect.min.x, rect.min.y));
+        if self.config.behavior.window_position != position {
+            self.config.behavior.window_position = position;
+            if let Err(e) = self.config.save() {
+                log::warn!("Failed to save config: {}", e);
+            }
+        }
+    }
+
+    /// Briefly show `message` in the reader window, so a trigger never goes unanswered
+    fn show_idle_message(&mut self, message: &str) {
+        self.state = AppState::Message(message.to_string(), std::time::Instant::now() + IDLE_MESSAGE_DURATION);
+    }
+
+    /// Start reading the `--url` article once it has downloaded, or say why it can't be
+    fn poll_article(&mut self) {
+        let Some(fetch) = &self.article_fetch else {
+            return;
+        };
+        let now = std::time::Instant::now();
+        match fetch.try_recv() {
+            Ok(Ok(text)) => {
+                self.article_fetch = None;
+                self.begin_reading(text);
+            }
+            Ok(Err(message)) => {
+                self.article_fetch = None;
+                self.state = AppState::Message(message, now + FETCH_ERROR_DURATION);
+            }
+            Err(std::sync::mpsc::TryRecvError::Empty) => self.show_idle_message(LOADING_ARTICLE),
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => self.article_fetch = None,
+        }
+    }
+
+    /// The reader card with `message` in place of a word
+    fn draw_idle_message(&self, ctx: &egui::Context, message: &str) {
+        let palette = self.config.display.palette();
+        let rgb = |[r, g, b]: [u8; 3]| apply_brightness(egui::Color32::from_rgb(r, g, b), self.config.display.brightness);
+        let bg_color = rgb(palette.background).linear_multiply(self.config.display.background_opacity);
+        let (border_color, text_color) = (rgb(palette.border), rgb(palette.text));
+        egui::CentralPanel::default()
+            .frame(egui::Frame::none().fill(egui::Color32::TRANSPARENT))
+            .show(ctx, |ui| {
+                let rect = ui.available_rect_before_wrap();
+                ui.painter().rect_filled(rect, egui::Rounding::same(12.0), bg_color);
+                ui.painter().rect_stroke(rect, egui::Rounding::same(12.0), egui::Stroke::new(1.0, border_color));
+                ui.painter().text(
+                    rect.center(),
+                    egui::Align2::CENTER_CENTER,
+                    message,
+                    egui::FontId::proportional(18.0 * self.config.display.font_scale()),
+                    text_color.linear_multiply(0.8),
+                );
+            });
+    }
+
+    /// Get the text to read: the current selection, or whatever is on the clipboard
+    fn read_selection(&self) -> Option<String> {
+        // Ask the focused app for its selection directly when we have permission;
+        // this leaves the clipboard untouched
+        #[cfg(all(target_os = "macos", feature = "accessibility"))]
+        if let Some(text) = accessibility::selected_text() {
+            return Some(text);
+        }
+
         // Small delay to ensure focus hasn't shifted to our app yet
         std::thread::sleep(Duration::from_millis(50));
 
-        // Simulate Cmd+C to copy any selected text
+        // The copy replaces whatever the user had on the clipboard; keep it to put back
+        let original = ClipboardContext::new().ok().and_then(|mut ctx| ctx.get_contents().ok());
+
+        // Simulate Cmd+C (Ctrl+C elsewhere) to copy any selected text
         #[cfg(target_os = "macos")]
         hotkey::simulate_copy();
+        #[cfg(target_os = "linux")]
+        x11_input::simulate_copy();
+        #[cfg(target_os = "windows")]
+        windows_input::simulate_copy();
 
         // Wait for copy to complete
         std::thread::sleep(Duration::from_millis(100));
 
         // Get clipboard content (either newly copied selection or existing content)
-        if let Ok(mut clipboard_ctx) = ClipboardContext::new() {
-            if let Ok(text) = clipboard_ctx.get_contents() {
-                if !text.is_empty() {
-                    let mut engine = RSVPEngine::new(
-                        &text,
-                        self.config.speed.start_wpm(),
-                        self.config.speed.target_wpm,
-                        self.config.speed.warmup_words,
-                    );
+        let text = self.read_clipboard();
+
+        // Put back the text the user had copied. Without any (empty, or an image and the
+        // like, which can't be put back), don't leave the selection there either.
+        match original {
+            Some(original) => {
+                if let Ok(mut watcher) = self.clipboard_watcher.lock() {
+                    watcher.ignore(&original);
+                }
+                if let Err(e) = ClipboardContext::new().and_then(|mut ctx| ctx.set_contents(original)) {
+                    log::warn!("Failed to restore the clipboard: {}", e);
+                }
+            }
+            // Nothing was copied, so the clipboard still holds what the user put there
+            None if text.is_none() => {}
+            None => clear_clipboard(),
+        }
+        text
+    }
 
-                    // If same text as before, restore position
-                    if self.last_text.as_ref() == Some(&text) && self.last_position > 0 {
-                        engine.seek_to(self.last_position);
-                    } else {
-                        // New text - reset saved position
-                        self.last_text = Some(text);
-                        self.last_position = 0;
-                    }
+    fn read_clipboard(&self) -> Option<String> {
+        let mut clipboard_ctx: ClipboardContext = ClipboardContext::new().ok()?;
+        let text = clipboard_ctx.get_contents().ok().unwrap_or_default();
+
+        // Our own copy (or a read we're already doing) must not re-trigger auto-read
+        if let Ok(mut watcher) = self.clipboard_watcher.lock() {
+            watcher.ignore(&text);
+        }
+
+        // Word processors also offer RTF, whose text survives better than their plain-text copy
+        #[cfg(target_os = "macos")]
+        if let Some(rtf_text) = macos_utils::pasteboard_rtf()
+            .map(|rtf| preprocess::rtf_to_text(&rtf))
+            .filter(|rtf_text| !rtf_text.trim().is_empty())
+        {
+            return Some(rtf_text);
+        }
+
+        Some(text).filter(|text| !text.is_empty())
+    }
+
+    /// Save the text being read, or else the clipboard text, to the reading queue
+    fn queue_text(&mut self) {
+        let text = if self.state.is_reading() {
+            self.last_text.clone()
+        } else {
+            self.read_clipboard()
+        };
+        let Some(text) = text else { return };
+
+        let mut queue = match ReadingQueue::load() {
+            Ok(queue) => queue,
+            Err(e) => {
+                log::warn!("Failed to load reading queue: {}", e);
+                return;
+            }
+        };
+        if !queue.enqueue(&text) {
+            log::warn!("Reading queue is full ({} items), text not added", ReadingQueue::MAX_ITEMS);
+            return;
+        }
+        if let Err(e) = queue.save() {
+            log::warn!("Failed to save reading queue: {}", e);
+        }
+    }
+
+    /// Start reading the oldest queued item, if there is one
+    fn start_queue(&mut self) {
+        let next = ReadingQueue::load()
+            .ok()
+            .and_then(|queue| queue.front().map(|item| item.text.clone()));
+        if let Some(text) = next {
+            self.begin_reading(text);
+            self.reading_queue = true;
+        }
+    }
+
+    /// Drop the item just finished from the queue and go on to the next one
+    fn advance_queue(&mut self) {
+        let Ok(mut queue) = ReadingQueue::load() else { return };
+        let next = queue.next_after_finish();
+        if let Err(e) = queue.save() {
+            log::warn!("Failed to save reading queue: {}", e);
+            return;
+        }
+        if let Some(text) = next {
+            self.begin_reading(text);
+            self.reading_queue = true;
+        }
+    }
+
+    /// Read the standard passage, then ask for a comprehension rating
+    fn start_calibration(&mut self) {
+        self.begin_reading(calibration::PASSAGE.to_string());
+        self.calibration = Some(Calibration::Reading);
+    }
+
+    /// Log the rating for the finished calibration read and close the reader
+    fn finish_calibration(&mut self, ctx: &egui::Context, wpm: u32, comprehension: u8) {
+        if let Err(e) = calibration::record(CalibrationRun { wpm, comprehension }) {
+            log::warn!("Failed to save calibration result: {}", e);
+        }
+        self.stop_reading(ctx);
+    }
+
+    /// Engine settings from the config for the current language profile
+    fn engine_builder<'a>(&self, text: &'a str) -> RSVPEngineBuilder<'a> {
+        let (start_rate, target_rate) = if self.profile.per_character_rate {
+            (self.config.language.start_cjk_cpm(), self.config.language.cjk_cpm)
+        } else {
+            (self.config.speed.start_wpm(), self.config.speed.target_wpm)
+        };
+
+        RSVPEngine::builder(text)
+            .tokenization(self.profile.tokenization)
+            .start_wpm(start_rate)
+            .target_wpm(target_rate)
+            .warmup_words(self.config.speed.warmup_words)
+            .wpm_range(self.config.speed.min_wpm, self.config.speed.max_wpm)
+            .end_hold(Duration::from_millis(self.config.speed.end_hold_ms))
+            .checkpoint_sentences(self.config.study_checkpoint_sentences)
+            .min_visible(Duration::from_millis(self.config.speed.min_visible_ms))
+            .display_limits(
+                Duration::from_millis(self.config.speed.min_display_ms),
+                Duration::from_millis(self.config.speed.max_display_ms),
+            )
+            .timing(self.config.timing.clone())
+            .seek_keeps_phase(self.config.speed.seek_keeps_phase)
+            .smooth_changes(self.config.speed.smooth_changes)
+            .rewind_on_resume(self.config.speed.rewind_on_resume)
+            .chunk_size(self.config.speed.chunk_size)
+            .max_word_chars(self.config.display.max_word_chars)
+            .review_words(self.config.display.review_words())
+            .orp_position(match self.config.display.orp_mode {
+                OrpMode::Table => None,
+                OrpMode::Proportional => Some(self.config.display.orp_position),
+            })
+    }
+
+    fn detect_profile(&mut self, text: &str) {
+        self.profile = if self.config.language.auto_detect {
+            LanguageProfile::detect(text)
+        } else {
+            LanguageProfile::default()
+        };
+    }
+
+    fn begin_reading(&mut self, text: String) {
+        let text = preprocess::apply(&text, &self.config.preprocess);
+        self.detect_profile(&text);
+        let mut engine = self.engine_builder(&text).build();
+
+        // If this text was read before, resume where it was left
+        if let Some(index) = self.positions.get(&notes::document_id(&text)) {
+            engine.seek_to(index);
+        } else if let Some(index) = self.position_in_longer_text(&text, &engine) {
+            engine.seek_to(index);
+        }
+        // Only what is left to read has to fit the time-box
+        if let Some(duration) = self.fit_duration {
+            engine.fit_to_duration(duration);
+        }
+        self.last_text = Some(text);
+
+        self.show_reader(engine);
+    }
+
+    /// Where the previous read left off inside `text`, when `text` contains the
+    /// previous text (say, the same article copied again with its intro)
+    fn position_in_longer_text(&self, text: &str, engine: &RSVPEngine) -> Option<usize> {
+        if !self.config.behavior.resume_in_longer_text {
+            return None;
+        }
+        let previous = self.last_text.as_deref().filter(|previous| text.contains(previous))?;
+        let position = self.positions.get(&notes::document_id(previous))?;
+        let previous_words = self.engine_builder(previous).build();
+        let offset = tokenizer::locate_run(engine.words(), previous_words.words())?;
+        Some(offset + position)
+    }
+
+    /// Remember where reading of the current text stopped (nowhere, once it's
+    /// finished) so it resumes there next time
+    fn save_position(&mut self, word_index: usize) {
+        let Some(text) = &self.last_text else {
+            return;
+        };
+        let document = notes::document_id(text);
+        if word_index == 0 {
+            self.positions.forget(&document);
+        } else {
+            self.positions.remember(&document, word_index);
+        }
+        if let Err(e) = self.positions.save() {
+            log::warn!("Failed to save reading positions: {}", e);
+        }
+    }
+
+    /// Reopen the read a crashed session left unfinished, starting paused
+    fn begin_recovered(&mut self, state: EngineState) {
+        let text = state.words.iter().map(|w| w.text.as_str()).collect::<Vec<_>>().join(" ");
+        self.detect_profile(&text);
+        let mut engine = self.engine_builder(&text).restore(state).build();
+        engine.pause();
+        self.paused = true;
+        self.last_text = None;
+        self.show_reader(engine);
+    }
+
+    /// Snapshot the current read for crash recovery
+    fn save_recovery(&mut self) {
+        if !self.config.behavior.crash_recovery {
+            return;
+        }
+        if let Some(engine) = &self.engine {
+            if let Err(e) = recovery::save(&engine.state()) {
+                log::warn!("Failed to save recovery file: {}", e);
+            }
+            self.recovery_saved_at = Some(std::time::Instant::now());
+        }
+    }
+
+    fn show_reader(&mut self, engine: RSVPEngine) {
+        let trainer = &self.config.display.focus_trainer;
+        self.focus_trainer = trainer
+            .enabled
+            .then(|| FocusTrainer::new(trainer, std::time::Instant::now()));
+
+        let lead_in = Duration::from_millis(self.config.speed.lead_in_ms);
+        self.lead_in_until = (!lead_in.is_zero()).then(|| std::time::Instant::now() + lead_in);
+
+        self.engine = Some(engine);
+        self.state = AppState::Reading;
+        self.had_focus = false; // Reset so we wait for focus before detecting loss
+        self.reading_stats.start_session();
+        self.read_since = Some(std::time::Instant::now());
+    }
+
+    /// Identifier of the text being read, for notes (None for a recovered read)
+    fn document_id(&self) -> Option<String> {
+        self.last_text.as_deref().map(notes::document_id)
+    }
+
+    /// Start typing a note for the current word; reading pauses meanwhile
+    fn begin_note(&mut self) {
+        if self.document_id().is_none() {
+            return;
+        }
+        if let Some(engine) = &mut self.engine {
+            engine.pause();
+            self.paused = true;
+            self.note_draft = Some(String::new());
+        }
+    }
+
+    fn save_note(&mut self) {
+        let (Some(draft), Some(document)) = (self.note_draft.take(), self.document_id()) else {
+            return;
+        };
+        let Some(index) = self.engine.as_ref().map(|e| e.get_current_index()) else {
+            return;
+        };
+        if self.notes.add(&document, index, &draft) {
+            if let Err(e) = self.notes.save() {
+                log::warn!("Failed to save notes: {}", e);
+            }
+        }
+    }
+
+    /// Jump to the first note after the current word (wrapping around) and show it
+    fn jump_to_next_note(&mut self) {
+        let Some(document) = self.document_id() else {
+            return;
+        };
+        let Some(engine) = &mut self.engine else {
+            return;
+        };
+        let notes = self.notes.for_document(&document);
+        let current = engine.get_current_index();
+        let Some((number, note)) = notes
+            .iter()
+            .enumerate()
+            .find(|(_, n)| n.word_index > current)
+            .or_else(|| notes.iter().enumerate().next())
+        else {
+            return;
+        };
+        engine.seek_to(note.word_index);
+        let banner = format!("Note {}/{}: {}", number + 1, notes.len(), note.text);
+        self.note_banner = Some((banner, std::time::Instant::now() + Duration::from_secs(4)));
+        self.show_seek_result();
+    }
+
+    /// How long the reader can go without a repaint: until the next word is due or the
+    /// next timed change on screen (lead-in, progress flash, banners, focus trainer).
+    /// Input repaints on its own, so a paused reader only checks in now and then.
+    fn next_repaint_in(&self, now: std::time::Instant) -> Duration {
+        let mut wait = self
+            .engine
+            .as_ref()
+            .and_then(|e| e.time_until_change_at(now))
+            .unwrap_or(IDLE_REPAINT_INTERVAL);
+        let trainer_change = self.focus_trainer.as_ref().filter(|_| !self.paused).map(|t| t.next_change(now));
+        let deadlines = [
+            self.lead_in_until,
+            self.progress_visible_until,
+            self.basis_label_until,
+            self.hud_visible_until,
+            self.note_banner.as_ref().map(|(_, until)| *until),
+            trainer_change,
+        ];
+        for deadline in deadlines.into_iter().flatten() {
+            wait = wait.min(deadline.saturating_duration_since(now));
+        }
+        wait
+    }
+
+    /// Persist the engine's target speed (CJK text is paced in characters per minute)
+    fn save_target_speed(&mut self) {
+        let Some(engine) = &self.engine else {
+            return;
+        };
+        if self.profile.per_character_rate {
+            self.config.language.cjk_cpm = engine.get_target_wpm();
+        } else {
+            self.config.speed.target_wpm = engine.get_target_wpm();
+        }
+        let _ = self.config.save();
+    }
+
+    /// Refresh the displayed word after a seek and flash the progress bar
+    fn show_seek_result(&mut self) {
+        if let Some(word) = self.engine.as_ref().and_then(|e| e.get_current_word()) {
+            self.last_word = screen_parts(word);
+        }
+        self.progress_visible_until = Some(std::time::Instant::now() + Duration::from_secs(1));
+    }
+
+    /// Add the words read through this session to today's tally
+    fn record_words_read(&mut self) {
+        let Some(engine) = &self.engine else {
+            return;
+        };
+        let words = engine.words_read() as u64;
+        let now = std::time::Instant::now();
+        let time = self.read_since.replace(now).map(|since| now - since).unwrap_or_default();
+        if words == 0 {
+            return;
+        }
+        self.reading_stats.add_words(&stats::today(), words);
+        self.reading_stats.add_reading_time(time);
+        if let Err(e) = self.reading_stats.save() {
+            log::warn!("Failed to save reading stats: {}", e);
+        }
+        #[cfg(target_os = "macos")]
+        if let Some(tray_icon) = &self.tray_icon {
+            let _ = tray_icon.set_tooltip(Some(tray_tooltip(&self.reading_stats, self.config.daily_goal)));
+        }
+        #[cfg(target_os = "macos")]
+        if let Some(tray_totals) = &self.tray_totals {
+            tray_totals.set_text(stats::total_text(self.reading_stats.total_words()));
+        }
+    }
+
+    /// Settings window, in its own viewport beside the reader. Edits the live config
+    /// (previewed on a sample word) and saves it after every change.
+    fn show_settings(&mut self, ctx: &egui::Context) {
+        if !self.settings_open {
+            return;
+        }
+        let config = &mut self.config;
+        let mut changed = false;
+        let mut close = false;
+
+        ctx.show_viewport_immediate(
+            egui::ViewportId::from_hash_of("settings"),
+            egui::ViewportBuilder::default()
+                .with_title("Speeder Settings")
+                .with_inner_size([420.0, 460.0]),
+            |ctx, _class| {
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    egui::Grid::new("settings").num_columns(2).spacing([16.0, 8.0]).show(ui, |ui| {
+                        ui.label("Target speed");
+                        let speed_range = config.speed.min_wpm..=config.speed.max_wpm;
+                        changed |= ui
+                            .add(egui::Slider::new(&mut config.speed.target_wpm, speed_range).suffix(" wpm"))
+                            .changed();
+                        ui.end_row();
+
+                        ui.label("Warm-up words");
+                        let warmup_range = 0..=SpeedConfig::MAX_WARMUP_WORDS;
+                        changed |= ui.add(egui::Slider::new(&mut config.speed.warmup_words, warmup_range)).changed();
+                        ui.end_row();
+
+                        ui.label("Start speed");
+                        let target_wpm = config.speed.target_wpm as f64;
+                        changed |= ui.add(
+                            egui::Slider::new(&mut config.speed.warmup_start, SpeedConfig::MIN_WARMUP_START..=1.0)
+                                .custom_formatter(|share, _| format!("{:.0} wpm", target_wpm * share)),
+                        ).changed();
+                        ui.end_row();
+
+                        ui.label("Font size");
+                        changed |= ui.add(egui::Slider::new(
+                            &mut config.display.font_size,
+                            DisplayConfig::MIN_FONT_SIZE..=DisplayConfig::MAX_FONT_SIZE,
+                        )).changed();
+                        ui.end_row();
+
+                        ui.label("ORP position");
+                        changed |= ui.add(egui::Slider::new(&mut config.display.orp_position, 0.0..=1.0)).changed();
+                        ui.end_row();
+
+                        ui.label("Theme");
+                        ui.horizontal(|ui| {
+                            for theme in Theme::ALL {
+                                changed |= ui.selectable_value(&mut config.display.theme, theme, theme.label()).changed();
+                            }
+                        });
+                        ui.end_row();
+
+                        ui.label("Brightness");
+                        changed |= ui.add(egui::Slider::new(
+                            &mut config.display.brightness,
+                            DisplayConfig::MIN_BRIGHTNESS..=DisplayConfig::MAX_BRIGHTNESS,
+                        )).changed();
+                        ui.end_row();
+
+                        for kind in WordKind::ALL {
+                            ui.label(format!("{} color", kind.label()));
+                            let mut color = config.display.focus_color(kind);
+                            if ui.color_edit_button_srgb(&mut color).changed() {
+                                config.display.focus_colors.insert(kind, color);
+                                changed = true;
+                            }
+                            ui.end_row();
+                        }
+                    });
+
+                    ui.separator();
+                    settings_preview(ui, config);
+                    ui.separator();
+
+                    close = ui.button("Close").clicked();
+                });
+
+                if ctx.input(|i| i.viewport().close_requested()) {
+                    close = true;
+                }
+            },
+        );
+
+        if changed {
+            config.clamp_to_limits();
+            if let Err(e) = config.save() {
+                log::warn!("Failed to save config: {}", e);
+            }
+        }
+        if close {
+            self.settings_open = false;
+        }
+    }
+
+    /// Pause the read and hide the reader, keeping the read to come back to
+    fn send_to_background(&mut self, ctx: &egui::Context) {
+        if let Some(engine) = &mut self.engine {
+            if !self.paused {
+                self.paused = true;
+                engine.pause();
+                self.save_recovery();
+            }
+        }
+        ctx.send_viewport_cmd(egui::ViewportCommand::Visible(false));
+        self.window_visible = false;
+        self.state = AppState::Backgrounded;
+    }
 
-                    self.engine = Some(engine);
-                    self.reading_active = true;
-                    self.had_focus = false; // Reset so we wait for focus before detecting loss
-                }
+    /// Show the reader again and carry on from the word it was paused on
+    fn bring_back(&mut self, ctx: &egui::Context) {
+        self.state = AppState::Reading;
+        self.show_window(ctx);
+        self.had_focus = false; // Wait for focus again before detecting loss
+        if let Some(engine) = &mut self.engine {
+            if !engine.is_at_checkpoint() {
+                self.paused = false;
+                engine.resume();
             }
         }
     }
 
-    fn stop_reading(&mut self, _ctx: &egui::Context) {
-        // Save current position before stopping
+    fn stop_reading(&mut self, ctx: &egui::Context) {
+        self.record_words_read();
+        self.save_window_position(ctx);
+        // Save current position before stopping; a finished text starts over next time
         if let Some(engine) = &self.engine {
-            self.last_position = engine.get_current_index();
+            let index = if engine.is_finished() { 0 } else { engine.get_current_index() };
+            self.save_position(index);
         }
         self.engine = None;
-        self.reading_active = false;
+        self.read_since = None;
+        self.state = AppState::Idle;
         self.paused = false;
         self.last_word = None;
         self.progress_visible_until = None;
+        self.basis_label_until = None;
+        self.hud_visible_until = None;
+        self.lead_in_until = None;
+        self.recovery_saved_at = None;
+        if self.config.behavior.crash_recovery {
+            if let Err(e) = recovery::clear() {
+                log::warn!("Failed to remove recovery file: {}", e);
+            }
+        }
+        self.focus_trainer = None;
+        self.skimming = false;
+        self.reading_queue = false;
+        self.calibration = None;
+        self.note_draft = None;
+        self.note_banner = None;
+        self.scroll_words = 0.0;
     }
 }
 
 impl eframe::App for SpeedReaderApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // Quitting stops the read first, so its stats and position are saved and the
+        // recovery file is cleared rather than left to look like a crash
+        if self.menu_flags.quit.swap(false, Ordering::Relaxed) {
+            if self.state.is_reading() {
+                self.stop_reading(ctx);
+            }
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            return;
+        }
+        if let Some(text) = self.launch_text.take() {
+            // Text named on the command line comes before an unfinished read
+            self.pending_recovery = None;
+            self.begin_reading(text);
+        } else if self.article_fetch.is_some() {
+            self.pending_recovery = None;
+            self.poll_article();
+        } else if let Some(state) = self.pending_recovery.take() {
+            self.begin_recovered(state);
+        }
+
         // Check for trigger from hotkey listener
-        if self.trigger_flag.swap(false, Ordering::Relaxed) && !self.reading_active {
-            self.start_reading(ctx);
+        if self.trigger_flag.swap(false, Ordering::Relaxed) {
+            if self.state == AppState::Backgrounded {
+                self.bring_back(ctx);
+            } else if !self.state.is_reading() {
+                self.start_reading(ctx);
+            }
+        }
+        if take_trigger(&self.copy_trigger_flag) && !self.state.is_reading() {
+            self.start_reading_clipboard();
+        }
+        if self.menu_flags.queue_add.swap(false, Ordering::Relaxed) {
+            self.queue_text();
+        }
+        if self.menu_flags.queue_read.swap(false, Ordering::Relaxed) && !self.state.is_reading() {
+            self.start_queue();
         }
+        if self.menu_flags.calibrate.swap(false, Ordering::Relaxed) && !self.state.is_reading() {
+            self.start_calibration();
+        }
+        if self.menu_flags.settings.swap(false, Ordering::Relaxed) {
+            self.settings_open = true;
+        }
+        self.show_settings(ctx);
 
         // Track focus state for ephemeral window behavior
         let has_focus = ctx.input(|i| i.focused);
+        if has_focus && !self.was_focused {
+            // Time spent hidden or unfocused shouldn't count toward the current word,
+            // otherwise reading jumps ahead the moment the window comes back
+            if let Some(engine) = &mut self.engine {
+                engine.reset_dwell();
+            }
+        }
+        self.was_focused = has_focus;
         if has_focus {
             self.had_focus = true;
         }
         // Only close when focus is *lost* (not when never gained)
-        if self.reading_active && self.had_focus && !has_focus {
-            self.stop_reading(ctx);
+        if self.state == AppState::Reading && self.had_focus && !has_focus {
+            match self.config.behavior.on_focus_loss {
+                FocusLossAction::Stop => self.stop_reading(ctx),
+                FocusLossAction::Pause => self.send_to_background(ctx),
+            }
+        }
+        if self.state == AppState::Backgrounded {
+            if !has_focus {
+                // Wait, hidden, for the hotkey
+                ctx.request_repaint_after(Duration::from_millis(100));
+                return;
+            }
+            self.bring_back(ctx);
         }
 
-        // If not reading, hide window and wait for hotkey
-        if !self.reading_active {
-            if self.window_visible {
-                ctx.send_viewport_cmd(egui::ViewportCommand::Visible(false));
-                self.window_visible = false;
+        // If not reading, hide window and wait for hotkey (once any hint has had its time)
+        if self.state != AppState::Reading {
+            let now = std::time::Instant::now();
+            match self.state.clone() {
+                AppState::Message(message, until) if now < until => {
+                    self.show_window(ctx);
+                    self.draw_idle_message(ctx, &message);
+                }
+                _ => {
+                    self.state = AppState::Idle;
+                    if self.window_visible {
+                        ctx.send_viewport_cmd(egui::ViewportCommand::Visible(false));
+                        self.window_visible = false;
+                    }
+                }
             }
             ctx.request_repaint_after(Duration::from_millis(100));
             return;
         }
 
         // Ensure window is visible during reading
-        if !self.window_visible {
-            // Position window centered on the screen containing the mouse cursor
+        self.show_window(ctx);
+
+        // Click-through: clicks reach the window beneath unless Option is held (to scrub or resize)
+        let click_through = self.config.display.click_through && !ctx.input(|i| i.modifiers.alt);
+        if click_through != self.click_through_applied {
             #[cfg(target_os = "macos")]
-            {
-                let (x, y) = macos_utils::get_centered_position_on_mouse_screen(700.0, 90.0);
-                ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(egui::pos2(x, y)));
-            }
-            ctx.send_viewport_cmd(egui::ViewportCommand::Visible(true));
-            ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
-            self.window_visible = true;
+            macos_utils::set_ignores_mouse_events(click_through);
+            self.click_through_applied = click_through;
         }
 
         // Handle keyboard input - collect actions first, then apply
         let mut should_toggle_pause = false;
         let mut should_stop = false;
         let mut should_restart = false;
+        let mut should_toggle_skim = false;
+        let mut should_toggle_basis = false;
+        let mut should_fit_duration = false;
+        let mut should_cycle_timing = false;
+        let mut should_toggle_click_through = false;
+        let mut should_toggle_hud = false;
         let mut speed_delta: i32 = 0;
+        let speed_step = |modifiers: &egui::Modifiers| self.config.speed.step(modifiers.shift, modifiers.alt);
+        let mut brightness_delta: f32 = 0.0;
 
         let mut seek_delta: i32 = 0;
+        let mut scroll_words: f32 = 0.0;
+        let mut sentence_seek: i32 = 0;
+        let mut seek_target: Option<SeekTarget> = None;
+        let mut number_key: Option<u8> = None; // A calibration rating, or else a speed preset
+        let mut should_add_note = false;
+        let mut should_jump_to_note = false;
+        let typing_note = self.note_draft.is_some();
         ctx.input(|i| {
+            // While a note is being typed, keys belong to its text field
+            if typing_note {
+                return;
+            }
             for event in &i.events {
-                if let egui::Event::Key { key, pressed: true, .. } = event {
+                if let egui::Event::Key { key, pressed: true, modifiers, .. } = event {
                     match key {
                         egui::Key::Space => should_toggle_pause = true,
                         egui::Key::Escape => should_stop = true,
                         egui::Key::R => should_restart = true,
-                        egui::Key::ArrowUp => speed_delta += 25,
-                        egui::Key::ArrowDown => speed_delta -= 25,
-                        egui::Key::ArrowLeft => seek_delta -= 1,
-                        egui::Key::ArrowRight => seek_delta += 1,
+                        egui::Key::S => should_toggle_skim = true,
+                        egui::Key::P => should_toggle_basis = true,
+                        egui::Key::T => should_fit_duration = true,
+                        egui::Key::M => should_cycle_timing = true,
+                        egui::Key::C => should_toggle_click_through = true,
+                        egui::Key::I => should_toggle_hud = true,
+                        egui::Key::N => should_add_note = true,
+                        egui::Key::L => should_jump_to_note = true,
+                        egui::Key::Home => seek_target = Some(SeekTarget::Start),
+                        egui::Key::End => seek_target = Some(SeekTarget::End),
+                        egui::Key::ArrowUp
+                        | egui::Key::ArrowDown
+                        | egui::Key::ArrowLeft
+                        | egui::Key::ArrowRight => match self.arrow_keys.action(*key) {
+                            // Shift steps finely and Alt (Option) coarsely
+                            Some(KeyAction::SpeedUp) => speed_delta += speed_step(modifiers) as i32,
+                            Some(KeyAction::SpeedDown) => speed_delta -= speed_step(modifiers) as i32,
+                            // With Shift the seek keys move by sentence
+                            Some(KeyAction::SeekBack) if modifiers.shift => sentence_seek -= 1,
+                            Some(KeyAction::SeekForward) if modifiers.shift => sentence_seek += 1,
+                            Some(KeyAction::SeekBack) => seek_delta -= 1,
+                            Some(KeyAction::SeekForward) => seek_delta += 1,
+                            None => {}
+                        },
+                        egui::Key::OpenBracket => brightness_delta -= 0.1,
+                        egui::Key::CloseBracket => brightness_delta += 0.1,
+                        egui::Key::Num1 => number_key = Some(1),
+                        egui::Key::Num2 => number_key = Some(2),
+                        egui::Key::Num3 => number_key = Some(3),
+                        egui::Key::Num4 => number_key = Some(4),
+                        egui::Key::Num5 => number_key = Some(5),
                         _ => {}
                     }
                 }
+                // Scrolling down (content moving up) reads on, scrolling up goes back
+                if let egui::Event::MouseWheel { unit, delta, .. } = event {
+                    scroll_words -= match unit {
+                        egui::MouseWheelUnit::Point => delta.y / SCROLL_POINTS_PER_WORD,
+                        egui::MouseWheelUnit::Line => delta.y,
+                        egui::MouseWheelUnit::Page => delta.y * SCROLL_PAGE_WORDS,
+                    };
+                }
             }
         });
 
+        // Calibration rating after the passage
+        if let (Some(Calibration::Rating { wpm }), Some(rating)) = (self.calibration, number_key) {
+            self.finish_calibration(ctx, wpm, rating);
+            return;
+        }
+
+        if should_add_note {
+            self.begin_note();
+        }
+        if should_jump_to_note {
+            self.jump_to_next_note();
+        }
+
         // Apply seek and show progress bar for 1 second
         if seek_delta != 0 {
             if let Some(engine) = &mut self.engine {
                 engine.seek(seek_delta);
-                // Update displayed word after seek
-                if let Some(word) = engine.get_current_word() {
-                    let (before, focus, after) = word.get_parts();
-                    self.last_word = Some((before, focus, after));
+                self.show_seek_result();
+            }
+        }
+
+        // Scrub by scrolling, one word per notch; trackpads build up to whole words
+        if scroll_words != 0.0 {
+            self.scroll_words += scroll_words;
+            let words = self.scroll_words.trunc();
+            if words != 0.0 {
+                self.scroll_words -= words;
+                if let Some(engine) = &mut self.engine {
+                    engine.seek(words as i32);
+                    self.show_seek_result();
                 }
-                self.progress_visible_until = Some(std::time::Instant::now() + Duration::from_secs(1));
+            }
+        }
+
+        // Jump by sentence
+        if sentence_seek != 0 {
+            if let Some(engine) = &mut self.engine {
+                engine.seek_sentence(sentence_seek.signum());
+                self.show_seek_result();
+            }
+        }
+
+        // Jump to the first or last word
+        if let Some(target) = seek_target {
+            if let Some(engine) = &mut self.engine {
+                engine.seek_to(target.index(engine.total_words()));
+                self.show_seek_result();
             }
         }
 
@@ -406,42 +2194,231 @@ impl eframe::App for SpeedReaderApp {
         if speed_delta != 0 {
             if let Some(engine) = &mut self.engine {
                 engine.adjust_speed(speed_delta);
-                // Persist the new target speed
-                self.config.speed.target_wpm = engine.get_target_wpm();
+                self.save_target_speed();
+            }
+        }
+
+        // Jump to a speed preset, flashing the HUD so the new speed shows
+        let preset = number_key.and_then(|n| self.config.speed.presets.get(n as usize - 1).copied());
+        if let Some(wpm) = preset {
+            if let Some(engine) = &mut self.engine {
+                engine.set_wpm(wpm);
+                self.save_target_speed();
+                self.hud_visible_until = Some(std::time::Instant::now() + Duration::from_secs(1));
+            }
+        }
+
+        // Toggle skimming (every Nth word)
+        if should_toggle_skim {
+            if let Some(engine) = &mut self.engine {
+                self.skimming = !self.skimming;
+                engine.set_stride(if self.skimming { self.config.speed.skim_stride } else { 1 });
+                self.progress_visible_until = Some(std::time::Instant::now() + Duration::from_secs(1));
+            }
+        }
+
+        // Pace the current text to finish in the time-box (not persisted)
+        if should_fit_duration {
+            if let Some(engine) = &mut self.engine {
+                let duration = self
+                    .fit_duration
+                    .unwrap_or(Duration::from_secs(self.config.speed.timebox_secs));
+                engine.fit_to_duration(duration);
+                self.progress_visible_until = Some(std::time::Instant::now() + Duration::from_secs(1));
+            }
+        }
+
+        // Step through the enabled timing modes (persisted)
+        if should_cycle_timing {
+            if let Some(engine) = &mut self.engine {
+                let timing = &mut self.config.timing;
+                timing.mode = timing.mode.next_in(&timing.cycle_modes);
+                engine.set_timing_mode(timing.mode);
                 let _ = self.config.save();
             }
         }
 
+        // Toggle click-through (persisted; applied next frame)
+        if should_toggle_click_through {
+            self.config.display.click_through = !self.config.display.click_through;
+            let _ = self.config.save();
+        }
+
+        // Show or hide the speed and position HUD (persisted)
+        if should_toggle_hud {
+            self.config.display.hud = !self.config.display.hud;
+            let _ = self.config.save();
+        }
+
+        // Flip the progress bar between words read and time elapsed (persisted)
+        if should_toggle_basis {
+            let display = &mut self.config.display;
+            display.progress_basis = display.progress_basis.toggled();
+            let _ = self.config.save();
+            let until = std::time::Instant::now() + Duration::from_secs(1);
+            self.progress_visible_until = Some(until);
+            self.basis_label_until = Some(until);
+        }
+
+        // Apply brightness changes from keyboard
+        if brightness_delta != 0.0 {
+            let display = &mut self.config.display;
+            display.brightness = (display.brightness + brightness_delta)
+                .clamp(DisplayConfig::MIN_BRIGHTNESS, DisplayConfig::MAX_BRIGHTNESS);
+            let _ = self.config.save();
+        }
+
         // Check if reading is finished
         if let Some(engine) = &self.engine {
-            if engine.is_finished() {
-                // Reset position so next time starts from beginning
-                self.last_position = 0;
+            if engine.is_finished() && self.calibration == Some(Calibration::Reading) {
+                // Keep the window up and ask how much was taken in
+                self.calibration = Some(Calibration::Rating { wpm: engine.get_target_wpm() });
+                self.calibration_suggestion = calibration::load_runs()
+                    .ok()
+                    .and_then(|runs| calibration::suggest_wpm(&runs));
+                self.save_position(0);
+                self.last_word = None;
+                self.record_words_read();
+                self.engine = None;
+            } else if engine.is_finished() {
+                // Stopping forgets the position, so next time starts from the beginning
+                let from_queue = self.reading_queue;
                 self.stop_reading(ctx);
-                return;
+                if from_queue {
+                    self.advance_queue();
+                }
+                if !self.state.is_reading() {
+                    return;
+                }
             }
         }
 
         // Colors
-        let bg_color = egui::Color32::from_rgb(20, 20, 25);
-        let border_color = egui::Color32::from_rgb(60, 60, 70);
-        let text_color = egui::Color32::from_rgb(200, 200, 210);
-        let focus_color = egui::Color32::from_rgb(255, 100, 100);
+        let brightness = self.config.display.brightness;
+        let palette = self.config.display.palette();
+        let rgb = |[r, g, b]: [u8; 3]| apply_brightness(egui::Color32::from_rgb(r, g, b), brightness);
+        // Only the card is translucent; its border and the words stay solid
+        let bg_color = rgb(palette.background).linear_multiply(self.config.display.background_opacity);
+        let border_color = rgb(palette.border);
+        let text_color = rgb(palette.text);
+        let focus_color = rgb(self.config.display.focus_color(WordKind::Normal));
+        let track_color = rgb(palette.track);
+
+        // Focus trainer fades the letters around the focus point (never while paused)
+        let context_alpha = match &mut self.focus_trainer {
+            Some(trainer) if !self.paused => trainer.context_alpha(std::time::Instant::now()),
+            _ => 1.0,
+        };
+        let context_color = text_color.linear_multiply(context_alpha);
+
+        // Hold the engine during the lead-in, then start the first word's dwell fresh
+        let lead_in = LeadIn::check(&mut self.lead_in_until, std::time::Instant::now());
+        if lead_in == LeadIn::JustEnded {
+            if let Some(engine) = &mut self.engine {
+                engine.reset_dwell();
+            }
+        }
+        let in_lead_in = lead_in == LeadIn::Holding;
 
         // Get word data and progress before UI rendering
-        if let Some(engine) = &mut self.engine {
+        if let Some(engine) = self.engine.as_mut().filter(|_| !in_lead_in) {
             if let Some(word) = engine.update() {
-                let (before, focus, after) = word.get_parts();
-                self.last_word = Some((before, focus, after));
+                self.last_word = screen_parts(word);
             }
+            // The engine pauses itself at study checkpoints
+            self.paused = engine.is_paused();
+        }
+
+        // Keep the crash recovery snapshot fresh
+        let snapshot_fresh = self.recovery_saved_at.is_some_and(|t| t.elapsed() < RECOVERY_SAVE_INTERVAL);
+        if !snapshot_fresh {
+            self.save_recovery();
         }
+        let at_checkpoint = self.engine.as_ref().is_some_and(|e| e.is_at_checkpoint());
+        let heading = self
+            .engine
+            .as_ref()
+            .and_then(|e| e.get_current_word())
+            .is_some_and(|w| w.heading);
+        let symbol = self
+            .engine
+            .as_ref()
+            .and_then(|e| e.get_current_word())
+            .is_some_and(|w| w.is_symbol());
+        // The focus letter's color tells numbers, sentence ends and headings apart
+        let letter_color = self
+            .engine
+            .as_ref()
+            .and_then(|e| e.get_current_word())
+            .map(|w| w.kind(&self.config.timing.sentence_terminators))
+            .map_or(focus_color, |kind| rgb(self.config.display.focus_color(kind)));
         let word_parts = self.last_word.clone();
+        let context = self
+            .engine
+            .as_ref()
+            .filter(|_| self.config.display.context_words > 0)
+            .map(|e| {
+                let display = &self.config.display;
+                // Once finished, the last word stays on screen
+                let index = e.get_current_index().min(e.total_words().saturating_sub(1));
+                context_words(e.words(), index, display.context_words, display.fade_read_context)
+            });
 
-        let (progress, _current_wpm) = if let Some(engine) = &self.engine {
-            (engine.get_progress(), engine.get_current_wpm())
+        // While paused, the whole sentence with the current word marked
+        let peek = self
+            .engine
+            .as_ref()
+            .filter(|e| self.paused && self.config.display.peek_on_pause && !e.is_at_checkpoint())
+            .and_then(|e| {
+                let mut bounds = e.sentence_bounds()?;
+                let index = e.get_current_index().min(bounds.end - 1);
+                // Text without punctuation is one long "sentence"; show the part around the word
+                bounds.start = bounds.start.max(index.saturating_sub(PEEK_MAX_WORDS / 2));
+                bounds.end = bounds.end.min(bounds.start + PEEK_MAX_WORDS);
+                let current = index - bounds.start;
+                let words: Vec<String> = e.words()[bounds].iter().map(|w| w.text.clone()).collect();
+                Some((words, current))
+            });
+        let word_joiner = match self.profile.tokenization {
+            Tokenization::Words => " ",
+            Tokenization::Characters => "",
+        };
+        // Holding B shows the words just read, to pick the thread back up
+        let review = self
+            .engine
+            .as_ref()
+            .filter(|_| !typing_note && peek.is_none() && ctx.input(|i| i.key_down(egui::Key::B)))
+            .map(|e| e.recent_words().to_vec())
+            .filter(|words| !words.is_empty());
+
+        let pause_factor = self
+            .engine
+            .as_ref()
+            .filter(|_| self.config.display.pause_cue && !self.paused)
+            .and_then(|e| e.upcoming_pause_factor());
+
+        let (progress, current_wpm) = if let Some(engine) = &self.engine {
+            (bar_progress(engine, self.config.display.progress_basis), engine.get_current_wpm())
         } else {
             (0.0, 0)
         };
+        let hud_flash = self.hud_visible_until.is_some_and(|t| std::time::Instant::now() < t);
+        let hud = self.engine.as_ref().filter(|_| self.config.display.hud || hud_flash).map(|engine| {
+            let unit = if self.profile.per_character_rate { "cpm" } else { "wpm" };
+            let display = &self.config.display;
+            let position = position_text(
+                display.position_style,
+                display.progress_basis,
+                engine.position(),
+                engine.total_words(),
+                engine.estimated_remaining(),
+            );
+            // Unless the position already says how long is left
+            let shows_time_left = (display.position_style, display.progress_basis)
+                == (PositionStyle::Remaining, ProgressBasis::Time);
+            let time_left = (!shows_time_left).then(|| engine.estimated_remaining());
+            hud_text(&position, current_wpm, unit, progress, time_left)
+        });
 
         // Apply keyboard actions
         if should_stop {
@@ -450,9 +2427,10 @@ impl eframe::App for SpeedReaderApp {
         }
 
         if should_restart {
+            // Restarting zeroes the session's count, so bank what was read first
+            self.record_words_read();
             if let Some(engine) = &mut self.engine {
                 engine.reset();
-                self.last_position = 0;
                 self.last_word = None;
             }
         }
@@ -462,6 +2440,7 @@ impl eframe::App for SpeedReaderApp {
                 self.paused = !self.paused;
                 if self.paused {
                     engine.pause();
+                    self.save_recovery();
                 } else {
                     engine.resume();
                     self.progress_visible_until = None;
@@ -478,6 +2457,15 @@ impl eframe::App for SpeedReaderApp {
 
         // Main reading interface
         let paused = self.paused;
+        let proportional_font = self.config.display.proportional_font;
+        let optical_center = self.config.display.optical_center;
+        let font_size = self.config.display.font_size;
+        let font_scale = self.config.display.font_scale();
+        let mut resize_to: Option<egui::Vec2> = None;
+        let mut resize_finished = false;
+        let mut scrub_to: Option<usize> = None; // Word clicked or dragged to on the progress bar
+        let total_words = self.engine.as_ref().map_or(0, |e| e.total_words());
+        let mut note_finished: Option<bool> = None; // Some(true) to save the note, Some(false) to drop it
         egui::CentralPanel::default()
             .frame(egui::Frame::none().fill(egui::Color32::TRANSPARENT))
             .show(ctx, |ui| {
@@ -498,42 +2486,234 @@ impl eframe::App for SpeedReaderApp {
                 );
 
                 // Center the word display
-                ui.vertical_centered(|ui| {
-                    ui.add_space((rect.height() - 45.0) / 2.0);
-
+                // Headings show larger (and bold in monospace)
+                let font_size = if heading { font_size * 40.0 / 34.0 } else { font_size };
+                if let Some((words, current)) = &peek {
+                    // Wrap the sentence to the card, shrinking it until it fits
+                    let wrap_width = rect.width() - 40.0 * font_scale;
+                    let mut size = font_size * PEEK_FONT_SCALE;
+                    let galley = loop {
+                        let mut job = egui::text::LayoutJob::default();
+                        job.wrap.max_width = wrap_width;
+                        job.halign = egui::Align::Center;
+                        for (i, word) in words.iter().enumerate() {
+                            let color = if i == *current { letter_color } else { text_color };
+                            let joiner = if i + 1 < words.len() { word_joiner } else { "" };
+                            let format = egui::TextFormat::simple(egui::FontId::proportional(size), color);
+                            job.append(&format!("{}{}", word, joiner), 0.0, format);
+                        }
+                        let galley = ui.fonts(|f| f.layout_job(job));
+                        if galley.size().y <= rect.height() - 24.0 * font_scale || size <= DisplayConfig::MIN_FONT_SIZE / 2.0 {
+                            break galley;
+                        }
+                        size *= 0.85;
+                    };
+                    let top = rect.center().y - galley.size().y / 2.0;
+                    ui.painter().galley(egui::pos2(rect.center().x, top), galley, text_color);
+                } else if symbol {
+                    // Emoji and symbols have no letter to fixate on; show them whole, centered
+                    if let Some((before, focus, after)) = word_parts {
+                        ui.painter().text(
+                            rect.center(),
+                            egui::Align2::CENTER_CENTER,
+                            format!("{}{}{}", before, focus, after),
+                            egui::FontId::proportional(font_size),
+                            text_color,
+                        );
+                    }
+                } else if let Some(context) = context {
+                    // Pin the focus glyph at the center and lay the word and its
+                    // context out on either side of it
+                    if let Some((before, focus, after)) = word_parts {
+                        let font_id = if proportional_font {
+                            egui::FontId::proportional(font_size)
+                        } else {
+                            egui::FontId::monospace(font_size)
+                        };
+                        let format = |alpha: f32| egui::TextFormat::simple(font_id.clone(), context_color.linear_multiply(alpha));
+
+                        let mut left = egui::text::LayoutJob::default();
+                        for (text, alpha) in &context.preceding {
+                            left.append(&format!("{} ", text), 0.0, format(*alpha));
+                        }
+                        left.append(&before, 0.0, format(1.0));
+                        let mut right = egui::text::LayoutJob::default();
+                        right.append(&after, 0.0, format(1.0));
+                        for (text, alpha) in &context.following {
+                            right.append(&format!(" {}", text), 0.0, format(*alpha));
+                        }
+
+                        let painter = ui.painter();
+                        let advance = ui.fonts(|f| f.glyph_width(&font_id, focus));
+                        let focus_left = rect.center().x - optical_nudge(advance, optical_center);
+                        let focus_galley = painter.layout_no_wrap(focus.to_string(), font_id.clone(), letter_color);
+                        let left_galley = ui.fonts(|f| f.layout_job(left));
+                        let right_galley = ui.fonts(|f| f.layout_job(right));
+                        let top = rect.center().y - focus_galley.size().y / 2.0;
+
+                        painter.galley(egui::pos2(focus_left - left_galley.size().x, top), left_galley, context_color);
+                        painter.galley(egui::pos2(focus_left, top), focus_galley, letter_color);
+                        painter.galley(egui::pos2(focus_left + advance, top), right_galley, context_color);
+                    }
+                } else if proportional_font {
+                    // Proportional glyphs vary in width, so lay the segments out around
+                    // a fixed pin at the window center instead of padding with spaces
                     if let Some((before, focus, after)) = word_parts {
-                        let font_size = 34.0;
+                        let font_id = egui::FontId::proportional(font_size);
+                        let painter = ui.painter();
+                        let advance = ui.fonts(|f| f.glyph_width(&font_id, focus));
+                        let focus_left = rect.center().x - optical_nudge(advance, optical_center);
+
+                        let focus_galley = painter.layout_no_wrap(focus.to_string(), font_id.clone(), letter_color);
+                        let before_galley = painter.layout_no_wrap(before, font_id.clone(), context_color);
+                        let after_galley = painter.layout_no_wrap(after, font_id, context_color);
+                        let top = rect.center().y - focus_galley.size().y / 2.0;
+
+                        painter.galley(egui::pos2(focus_left - before_galley.size().x, top), before_galley, context_color);
+                        painter.galley(egui::pos2(focus_left, top), focus_galley, letter_color);
+                        painter.galley(egui::pos2(focus_left + advance, top), after_galley, context_color);
+                    }
+                } else {
+                    ui.vertical_centered(|ui| {
+                        ui.add_space((rect.height() - 45.0 * font_scale) / 2.0);
 
-                        ui.horizontal(|ui| {
-                            ui.add_space((ui.available_width() - 580.0).max(0.0) / 2.0);
+                        if let Some((before, focus, after)) = word_parts {
+                            ui.horizontal(|ui| {
+                                ui.add_space((ui.available_width() - 580.0 * font_scale).max(0.0) / 2.0);
 
-                            ui.label(
-                                egui::RichText::new(format!("{:>12}", before))
+                                let mut before = egui::RichText::new(format!("{:>12}", before))
                                     .size(font_size)
-                                    .color(text_color)
-                                    .monospace(),
-                            );
-
-                            ui.label(
-                                egui::RichText::new(focus.to_string())
+                                    .color(context_color)
+                                    .monospace();
+                                if heading {
+                                    before = before.strong();
+                                }
+                                ui.label(before);
+
+                                ui.label(
+                                    egui::RichText::new(focus.to_string())
+                                        .size(font_size)
+                                        .color(letter_color)
+                                        .monospace()
+                                        .strong(),
+                                );
+
+                                let mut after = egui::RichText::new(format!("{:<12}", after))
                                     .size(font_size)
-                                    .color(focus_color)
-                                    .monospace()
-                                    .strong(),
-                            );
+                                    .color(context_color)
+                                    .monospace();
+                                if heading {
+                                    after = after.strong();
+                                }
+                                ui.label(after);
+                            });
+                        }
+                    });
+                }
 
-                            ui.label(
-                                egui::RichText::new(format!("{:<12}", after))
-                                    .size(font_size)
-                                    .color(text_color)
-                                    .monospace(),
-                            );
-                        });
+                // Recently shown words, in a faded line above the current one
+                if let Some(words) = &review {
+                    let font_id = egui::FontId::proportional(font_size * REVIEW_FONT_SCALE);
+                    let color = text_color.linear_multiply(0.5);
+                    // The most recent words matter most; drop the oldest until the line fits
+                    let mut first = 0;
+                    let galley = loop {
+                        let line = words[first..].join(word_joiner);
+                        let galley = ui.painter().layout_no_wrap(line, font_id.clone(), color);
+                        if galley.size().x <= rect.width() - 24.0 || first + 1 >= words.len() {
+                            break galley;
+                        }
+                        first += 1;
+                    };
+                    let bottom = rect.center().y - font_size * 0.6;
+                    let pos = egui::pos2(rect.center().x - galley.size().x / 2.0, bottom - galley.size().y);
+                    ui.painter().galley(pos, galley, color);
+                }
+
+                // Always-on HUD in the top-left corner, kept small and dim
+                if let Some(hud) = &hud {
+                    ui.painter().text(
+                        egui::pos2(rect.left() + 10.0, rect.top() + 8.0),
+                        egui::Align2::LEFT_TOP,
+                        hud,
+                        egui::FontId::monospace(10.0),
+                        text_color.linear_multiply(0.35),
+                    );
+                }
+
+                // Calibration rating prompt
+                if let Some(Calibration::Rating { wpm }) = self.calibration {
+                    let prompt = match self.calibration_suggestion {
+                        Some(best) => format!("Read at {} wpm. How much did you take in? 1-5 (best so far: {} wpm)", wpm, best),
+                        None => format!("Read at {} wpm. How much did you take in? 1-5", wpm),
+                    };
+                    ui.painter().text(
+                        rect.center(),
+                        egui::Align2::CENTER_CENTER,
+                        prompt,
+                        egui::FontId::proportional(14.0),
+                        text_color,
+                    );
+                }
+
+                // Note field for the current word: Enter saves, Escape cancels
+                if let Some(draft) = &mut self.note_draft {
+                    let field_rect = egui::Rect::from_min_size(
+                        egui::pos2(rect.left() + 12.0, rect.bottom() - 30.0),
+                        egui::vec2(rect.width() - 24.0, 22.0),
+                    );
+                    let field = ui.put(
+                        field_rect,
+                        egui::TextEdit::singleline(draft).hint_text("Note for this word (Enter to save, Esc to cancel)"),
+                    );
+                    field.request_focus();
+                    if ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                        note_finished = Some(true);
+                    } else if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                        note_finished = Some(false);
                     }
-                });
+                }
+
+                // Note jumped to with `L`
+                if let Some((banner, until)) = &self.note_banner {
+                    if std::time::Instant::now() < *until {
+                        ui.painter().text(
+                            egui::pos2(rect.center().x, rect.top() + 10.0),
+                            egui::Align2::CENTER_TOP,
+                            banner,
+                            egui::FontId::proportional(12.0),
+                            text_color.linear_multiply(0.8),
+                        );
+                    }
+                }
+
+                // Study checkpoint prompt
+                if at_checkpoint {
+                    ui.painter().text(
+                        egui::pos2(rect.center().x, rect.top() + 10.0),
+                        egui::Align2::CENTER_TOP,
+                        "Continue? (Space)",
+                        egui::FontId::proportional(12.0),
+                        text_color.linear_multiply(0.6),
+                    );
+                }
 
                 // Slim progress bar at the bottom when paused or recently scrolled
                 let show_bar = paused || self.progress_visible_until.map(|t| std::time::Instant::now() < t).unwrap_or(false);
+
+                // Pause cue: a short bar under the focus point that warms before long pauses
+                if let Some(factor) = pause_factor.filter(|_| !show_bar) {
+                    let cue_rect = egui::Rect::from_center_size(
+                        egui::pos2(rect.center().x, rect.bottom() - 9.0),
+                        egui::vec2(24.0, 2.0),
+                    );
+                    ui.painter().rect_filled(
+                        cue_rect,
+                        egui::Rounding::same(1.0),
+                        pause_cue_color(factor, track_color, focus_color),
+                    );
+                }
+
                 if show_bar {
                     let bar_height = 2.0;
                     let bar_margin = 12.0;
@@ -548,9 +2728,21 @@ impl eframe::App for SpeedReaderApp {
                     ui.painter().rect_filled(
                         track_rect,
                         egui::Rounding::same(1.5),
-                        egui::Color32::from_rgb(40, 40, 50),
+                        track_color,
                     );
 
+                    // Click or drag along the bar to scrub; the hit area is taller than the bar
+                    let scrubber = ui.interact(
+                        track_rect.expand2(egui::vec2(0.0, PROGRESS_HIT_MARGIN)),
+                        ui.id().with("progress_scrub"),
+                        egui::Sense::click_and_drag(),
+                    );
+                    if scrubber.clicked() || scrubber.dragged() {
+                        if let Some(pos) = scrubber.interact_pointer_pos() {
+                            scrub_to = Some(track_index(pos.x, track_rect, total_words));
+                        }
+                    }
+
                     // Progress fill
                     let fill_rect = egui::Rect::from_min_size(
                         egui::pos2(rect.left() + bar_margin, bar_y),
@@ -561,16 +2753,79 @@ impl eframe::App for SpeedReaderApp {
                         egui::Rounding::same(1.5),
                         focus_color.linear_multiply(0.8),
                     );
+
+                    let show_label = self.basis_label_until.is_some_and(|t| std::time::Instant::now() < t);
+                    if show_label {
+                        ui.painter().text(
+                            egui::pos2(rect.left() + bar_margin, bar_y - 4.0),
+                            egui::Align2::LEFT_BOTTOM,
+                            self.config.display.progress_basis.label(),
+                            egui::FontId::proportional(10.0),
+                            text_color.linear_multiply(0.6),
+                        );
+                    }
                 }
 
+                // Resize handle in the bottom-right corner (the window has no decorations)
+                let handle_rect = egui::Rect::from_min_size(
+                    rect.right_bottom() - egui::vec2(RESIZE_HANDLE_SIZE, RESIZE_HANDLE_SIZE),
+                    egui::vec2(RESIZE_HANDLE_SIZE, RESIZE_HANDLE_SIZE),
+                );
+                let handle = ui
+                    .interact(handle_rect, ui.id().with("resize_handle"), egui::Sense::drag())
+                    .on_hover_cursor(egui::CursorIcon::ResizeNwSe);
+                if handle.dragged() {
+                    resize_to = Some(rect.size() + handle.drag_delta());
+                }
+                resize_finished = handle.drag_stopped();
+                if handle.hovered() || handle.dragged() {
+                    for offset in [4.0, 8.0] {
+                        ui.painter().line_segment(
+                            [
+                                egui::pos2(rect.right() - offset, rect.bottom() - 3.0),
+                                egui::pos2(rect.right() - 3.0, rect.bottom() - offset),
+                            ],
+                            egui::Stroke::new(1.0, border_color),
+                        );
+                    }
+                }
             });
 
-        ctx.request_repaint();
+        match note_finished {
+            Some(true) => self.save_note(),
+            Some(false) => self.note_draft = None,
+            None => {}
+        }
+
+        if let Some(index) = scrub_to {
+            if let Some(engine) = &mut self.engine {
+                if index != engine.get_current_index() {
+                    engine.seek_to(index);
+                }
+                self.show_seek_result();
+            }
+        }
+
+        if let Some(size) = resize_to {
+            let (width, height) = DisplayConfig::clamp_window_size(size.x, size.y);
+            ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(egui::vec2(width, height)));
+        }
+        if resize_finished {
+            // Persist the final size so the next launch opens at it
+            let size = ctx.input(|i| i.viewport().inner_rect).map(|r| r.size());
+            if let Some(size) = size {
+                self.config.display.set_window_size(size.x, size.y);
+                let _ = self.config.save();
+            }
+        }
+
+        ctx.request_repaint_after(self.next_repaint_in(std::time::Instant::now()));
     }
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     env_logger::init();
+    let launch = LaunchOptions::parse(std::env::args().skip(1));
 
     // Set app to accessory mode (no dock icon, no cmd-tab)
     #[cfg(target_os = "macos")]
@@ -579,48 +2834,150 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Load configuration
     let config = Config::load().unwrap_or_default();
 
+    if let Some(path) = &launch.bench_tokenize {
+        return run_tokenize_bench(path, &config);
+    }
+    let launch_text = match &launch.read_file {
+        Some(path) => Some(read_launch_text(path).map_err(|e| format!("Can't read {}: {}", path.display(), e))?),
+        None => None,
+    };
+    // Download in the background so the reader can say it's loading, or why it failed
+    let article_fetch = launch.read_url.clone().map(|url| {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let result = article::fetch(&url).map_err(|e| format!("Couldn't load the article: {}", e));
+            if let Err(message) = &result {
+                log::warn!("{} ({})", message, url);
+            }
+            let _ = sender.send(result);
+        });
+        receiver
+    });
+
+    // Shared flag for hotkey trigger
+    let trigger_flag = Arc::new(AtomicBool::new(false));
+
+    // Set up the global hotkey with the platform's backend,
+    // falling back to the alternate combo if the first is taken
+    #[cfg_attr(not(target_os = "macos"), allow(unused_variables))]
+    let hotkey_status = {
+        let start_reading = Hotkey::parse(&config.hotkeys.start_reading).unwrap_or_else(|e| {
+            let default = Hotkey::default_start_reading();
+            eprintln!("Invalid hotkeys.start_reading ({}), using {}", e, default);
+            default
+        });
+        let mut candidates = vec![start_reading];
+        if !config.hotkeys.start_reading_fallback.is_empty() {
+            match Hotkey::parse(&config.hotkeys.start_reading_fallback) {
+                Ok(fallback) => candidates.push(fallback),
+                Err(e) => eprintln!("Ignoring hotkeys.start_reading_fallback: {}", e),
+            }
+        }
+        match PlatformHotkeys.register(Arc::clone(&trigger_flag), &candidates) {
+            Ok(hotkey) => {
+                log::info!("Reading hotkey: {}", hotkey);
+                format!("{} to read", hotkey)
+            }
+            Err(message) => {
+                eprintln!("{}", message);
+                "Reading hotkey unavailable (see log)".to_string()
+            }
+        }
+    };
+
+    let reading_stats = ReadingStats::load().unwrap_or_else(|e| {
+        log::warn!("Failed to load reading stats: {}", e);
+        ReadingStats::default()
+    });
+
     // Create menubar tray icon
     #[cfg(target_os = "macos")]
-    let _tray_icon = {
+    let tray_totals = MenuItem::with_id("totals", stats::total_text(reading_stats.total_words()), false, None);
+    #[cfg(target_os = "macos")]
+    let tray_icon = {
         let menu = Menu::new();
-        let _ = menu.append(&MenuItem::with_id("status", "Cmd+Ctrl+R to read", false, None));
+        let _ = menu.append(&MenuItem::with_id("status", hotkey_status, false, None));
+        let _ = menu.append(&tray_totals);
+        let _ = menu.append(&PredefinedMenuItem::separator());
+        let _ = menu.append(&MenuItem::with_id("queue_add", "Queue Clipboard Text", true, None));
+        let _ = menu.append(&MenuItem::with_id("queue_read", "Read Queue", true, None));
+        let _ = menu.append(&MenuItem::with_id("calibrate", "Calibrate Speed", true, None));
+        let _ = menu.append(&MenuItem::with_id("settings", "Settings…", true, None));
         let _ = menu.append(&PredefinedMenuItem::separator());
         let _ = menu.append(&MenuItem::with_id("quit", "Quit Speeder", true, None));
 
         TrayIconBuilder::new()
             .with_menu(Box::new(menu))
-            .with_tooltip("Speeder - Speed Reader")
+            .with_tooltip(tray_tooltip(&reading_stats, config.daily_goal))
             .with_title("Speeder")  // macOS menubar text
             .build()
             .expect("Failed to create tray icon")
     };
 
     // Handle tray menu events in a separate thread
+    let menu_flags = MenuFlags::default();
     #[cfg(target_os = "macos")]
     {
         use tray_icon::menu::MenuEvent;
-        std::thread::spawn(|| {
+        let menu_flags = menu_flags.clone();
+        std::thread::spawn(move || {
             loop {
                 if let Ok(event) = MenuEvent::receiver().recv() {
-                    if event.id.0 == "quit" {
-                        std::process::exit(0);
+                    match event.id.0.as_str() {
+                        "quit" => menu_flags.quit.store(true, Ordering::Relaxed),
+                        "queue_add" => menu_flags.queue_add.store(true, Ordering::Relaxed),
+                        "queue_read" => menu_flags.queue_read.store(true, Ordering::Relaxed),
+                        "calibrate" => menu_flags.calibrate.store(true, Ordering::Relaxed),
+                        "settings" => menu_flags.settings.store(true, Ordering::Relaxed),
+                        _ => {}
                     }
                 }
             }
         });
     }
 
-    // Shared flag for hotkey trigger
-    let trigger_flag = Arc::new(AtomicBool::new(false));
 
-    // Set up global hotkey using Carbon API (doesn't block window focus)
-    #[cfg(target_os = "macos")]
-    hotkey::setup_global_hotkey(Arc::clone(&trigger_flag));
+    // Watch the clipboard for newly copied text when auto-read is enabled
+    let copy_trigger_flag = Arc::new(AtomicBool::new(false));
+    let clipboard_watcher = Arc::new(Mutex::new(ClipboardWatcher::new(
+        config.auto_read_min_chars,
+        Duration::from_millis(300),
+    )));
+    if config.auto_read_on_copy {
+        let flag = Arc::clone(&copy_trigger_flag);
+        let watcher = Arc::clone(&clipboard_watcher);
+        std::thread::spawn(move || {
+            let Ok(mut clipboard_ctx) = ClipboardContext::new() else {
+                eprintln!("Clipboard unavailable, auto-read disabled");
+                return;
+            };
+            loop {
+                if let Ok(text) = clipboard_ctx.get_contents() {
+                    let triggered = watcher
+                        .lock()
+                        .map(|mut w| w.observe(&text, std::time::Instant::now()))
+                        .unwrap_or(false);
+                    if triggered {
+                        flag.store(true, Ordering::Relaxed);
+                    }
+                }
+                std::thread::sleep(Duration::from_millis(250));
+            }
+        });
+    }
+
+    // Read the clipboard straight away instead of waiting for the hotkey (one-shot: the flag is taken)
+    if launch_trigger(&config, &launch) {
+        copy_trigger_flag.store(true, Ordering::Relaxed);
+    }
 
     // Run the GUI app with transparent background
+    let (reader_width, reader_height) = config.display.reader_size();
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
-            .with_inner_size([700.0, 90.0])
+            .with_inner_size([reader_width, reader_height])
+            .with_min_inner_size([DisplayConfig::MIN_WINDOW_WIDTH, DisplayConfig::MIN_WINDOW_HEIGHT])
+            .with_resizable(true)
             .with_decorations(false)
             .with_transparent(true)
             .with_always_on_top(),
@@ -630,8 +2987,321 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     eframe::run_native(
         "Speeder",
         options,
-        Box::new(move |_cc| Ok(Box::new(SpeedReaderApp::new(trigger_flag, config)))),
+        Box::new(move |_cc| {
+            let mut app = SpeedReaderApp::new(
+                trigger_flag,
+                copy_trigger_flag,
+                clipboard_watcher,
+                menu_flags,
+                launch.duration,
+                reading_stats,
+                config,
+            );
+            app.launch_text = launch_text;
+            app.article_fetch = article_fetch;
+            #[cfg(target_os = "macos")]
+            {
+                app.tray_icon = Some(tray_icon);
+                app.tray_totals = Some(tray_totals);
+            }
+            Ok(Box::new(app))
+        }),
     )?;
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn brightness_scales_colors_toward_black_or_white() {
+        let color = egui::Color32::from_rgb(200, 100, 0);
+        assert_eq!(apply_brightness(color, 1.0), color);
+        assert_eq!(apply_brightness(color, 0.5), egui::Color32::from_rgb(100, 50, 0));
+        assert_eq!(apply_brightness(color, 1.5), egui::Color32::from_rgb(228, 178, 128));
+    }
+
+    #[test]
+    fn brightness_keeps_translucency() {
+        let color = egui::Color32::from_rgba_unmultiplied(200, 100, 0, 128);
+        let [r, g, b, a] = apply_brightness(color, 0.5).to_srgba_unmultiplied();
+        assert_eq!(a, 128);
+        for (channel, expected) in [(r, 100), (g, 50), (b, 0)] {
+            assert!(channel.abs_diff(expected) <= 1, "{} vs {}", channel, expected);
+        }
+    }
+
+    #[test]
+    fn brightness_is_clamped() {
+        let color = egui::Color32::from_rgb(200, 100, 50);
+        assert_eq!(apply_brightness(color, 0.0), apply_brightness(color, DisplayConfig::MIN_BRIGHTNESS));
+        assert_eq!(apply_brightness(color, 9.0), apply_brightness(color, DisplayConfig::MAX_BRIGHTNESS));
+    }
+
+    #[test]
+    fn arrow_keys_resolve_to_configured_actions() {
+        let hotkeys = HotkeyConfig {
+            arrow_up: "seek_back".to_string(),
+            arrow_down: " Seek_Forward ".to_string(),
+            arrow_left: "speed_down".to_string(),
+            arrow_right: "speed_up".to_string(),
+            ..HotkeyConfig::default()
+        };
+        let map = ArrowKeyMap::from_config(&hotkeys);
+        assert_eq!(map.action(egui::Key::ArrowUp), Some(KeyAction::SeekBack));
+        assert_eq!(map.action(egui::Key::ArrowDown), Some(KeyAction::SeekForward));
+        assert_eq!(map.action(egui::Key::ArrowLeft), Some(KeyAction::SpeedDown));
+        assert_eq!(map.action(egui::Key::ArrowRight), Some(KeyAction::SpeedUp));
+        assert_eq!(map.action(egui::Key::Space), None);
+    }
+
+    #[test]
+    fn unknown_arrow_action_keeps_the_stock_binding() {
+        let hotkeys = HotkeyConfig { arrow_up: "warp_speed".to_string(), ..HotkeyConfig::default() };
+        let map = ArrowKeyMap::from_config(&hotkeys);
+        assert_eq!(map.action(egui::Key::ArrowUp), Some(KeyAction::SpeedUp));
+        assert_eq!(map.action(egui::Key::ArrowLeft), Some(KeyAction::SeekBack));
+    }
+
+    /// Start and end of each dimmed period in the first `span`, sampled every 10 ms
+    fn dimmed_periods(config: &FocusTrainerConfig, span: Duration) -> Vec<(Duration, Duration)> {
+        let start = std::time::Instant::now();
+        let mut trainer = FocusTrainer::new(config, start);
+        let mut periods = Vec::new();
+        let mut dimmed_since = None;
+        let step = Duration::from_millis(10);
+        let mut at = Duration::ZERO;
+        while at < span {
+            match (trainer.is_dimmed(start + at), dimmed_since) {
+                (true, None) => dimmed_since = Some(at),
+                (false, Some(since)) => {
+                    periods.push((since, at));
+                    dimmed_since = None;
+                }
+                _ => {}
+            }
+            at += step;
+        }
+        periods
+    }
+
+    #[test]
+    fn focus_trainer_dims_for_the_set_duration_at_varied_intervals() {
+        let config = FocusTrainerConfig { enabled: true, interval_ms: 1000, duration_ms: 200, seed: 7 };
+        let periods = dimmed_periods(&config, Duration::from_secs(30));
+        assert!(periods.len() >= 15, "{} periods", periods.len());
+
+        let mut previous_end = Duration::ZERO;
+        for &(start, end) in &periods {
+            let length = (end - start).as_millis();
+            assert!((190..=210).contains(&length), "dimmed for {} ms", length);
+            let gap = (start - previous_end).as_millis();
+            assert!((490..=1510).contains(&gap), "gap of {} ms", gap);
+            previous_end = end;
+        }
+        let gaps: Vec<_> = periods.windows(2).map(|pair| pair[1].0 - pair[0].1).collect();
+        assert!(gaps.windows(2).any(|pair| pair[0] != pair[1]), "gaps never vary");
+    }
+
+    #[test]
+    fn focus_trainer_schedule_follows_its_seed() {
+        let config = FocusTrainerConfig { enabled: true, interval_ms: 1000, duration_ms: 200, seed: 7 };
+        let other = FocusTrainerConfig { seed: 8, ..config.clone() };
+        let span = Duration::from_secs(10);
+        assert_eq!(dimmed_periods(&config, span), dimmed_periods(&config, span));
+        assert_ne!(dimmed_periods(&config, span), dimmed_periods(&other, span));
+    }
+
+    #[test]
+    fn focus_trainer_next_change_is_the_next_edge() {
+        let config = FocusTrainerConfig { enabled: true, interval_ms: 1000, duration_ms: 200, seed: 1 };
+        let start = std::time::Instant::now();
+        let mut trainer = FocusTrainer::new(&config, start);
+        let dims_at = trainer.next_change(start);
+        assert!(!trainer.is_dimmed(dims_at - Duration::from_millis(1)));
+        assert!(trainer.is_dimmed(dims_at));
+        assert_eq!(trainer.next_change(dims_at), dims_at + Duration::from_millis(200));
+        assert_eq!(trainer.context_alpha(dims_at), FocusTrainer::DIM_ALPHA);
+    }
+
+    #[test]
+    fn optical_nudge_centers_the_focus_glyph_on_the_pin() {
+        assert_eq!(optical_nudge(18.0, true), 9.0);
+        assert_eq!(optical_nudge(7.0, true), 3.5);
+        assert_eq!(optical_nudge(18.0, false), 0.0);
+    }
+
+    #[test]
+    fn home_and_end_seek_to_the_first_and_last_word() {
+        let mut engine = RSVPEngine::new("one two three four", 300, 300, 0);
+        engine.seek_to(SeekTarget::End.index(engine.total_words()));
+        assert_eq!(engine.get_current_index(), 3);
+        engine.seek_to(SeekTarget::End.index(engine.total_words()));
+        assert_eq!(engine.get_current_index(), 3);
+        engine.seek_to(SeekTarget::Start.index(engine.total_words()));
+        assert_eq!(engine.get_current_index(), 0);
+        engine.seek_to(SeekTarget::Start.index(engine.total_words()));
+        assert_eq!(engine.get_current_index(), 0);
+    }
+
+    #[test]
+    fn home_and_end_in_an_empty_text_stay_at_zero() {
+        let mut engine = RSVPEngine::new("", 300, 300, 0);
+        for target in [SeekTarget::Start, SeekTarget::End] {
+            assert_eq!(target.index(engine.total_words()), 0);
+            engine.seek_to(target.index(engine.total_words()));
+            assert_eq!(engine.get_current_index(), 0);
+        }
+    }
+
+    #[test]
+    fn pause_cue_warms_toward_a_sentence_end() {
+        let calm = egui::Color32::from_rgb(0, 0, 255);
+        let warm = egui::Color32::from_rgb(255, 0, 0);
+        assert_eq!(pause_cue_color(1.0, calm, warm), calm);
+        assert_eq!(pause_cue_color(0.8, calm, warm), calm); // Shorter than a plain word
+        assert_eq!(pause_cue_color(1.6, calm, warm), warm);
+        assert_eq!(pause_cue_color(3.0, calm, warm), warm);
+        let halfway = pause_cue_color(1.3, calm, warm);
+        assert!(halfway != calm && halfway != warm);
+        assert!(pause_cue_color(1.15, calm, warm).r() < halfway.r());
+    }
+
+    #[test]
+    fn hud_text_shows_position_speed_and_percent() {
+        assert_eq!(hud_text("142/980", 420, "wpm", 0.384, None), "142/980 · 420wpm · 38%");
+        assert_eq!(hud_text("1/3", 900, "cpm", 1.2, None), "1/3 · 900cpm · 100%");
+        assert_eq!(
+            hud_text("142/980", 420, "wpm", 0.5, Some(Duration::from_secs(130))),
+            "142/980 · 420wpm · 50% · 2:10 left"
+        );
+    }
+
+    #[test]
+    fn words_lay_out_in_their_own_direction() {
+        let english = Word::new("reading".to_string());
+        assert_eq!(screen_parts(&english), Some(("re".to_string(), 'a', "ding".to_string())));
+        let arabic = Word::new("مرحبا".to_string());
+        assert_eq!(arabic.direction, TextDirection::Rtl);
+        // The reading start sits on the right of the focus letter
+        assert_eq!(screen_parts(&arabic), Some(("ابح".to_string(), 'ر', "م".to_string())));
+    }
+
+    #[test]
+    fn progress_bar_follows_the_basis() {
+        let mut engine = RSVPEngine::new("a b c extraordinarily, unquestionably.", 300, 300, 0);
+        engine.seek_to(3);
+        assert_eq!(bar_progress(&engine, ProgressBasis::Words), engine.get_progress());
+        assert_eq!(bar_progress(&engine, ProgressBasis::Time), engine.time_progress());
+        assert_ne!(bar_progress(&engine, ProgressBasis::Words), bar_progress(&engine, ProgressBasis::Time));
+    }
+
+    #[test]
+    fn lead_in_holds_the_first_word_until_it_runs_out() {
+        let start = std::time::Instant::now();
+        let mut until = Some(start + Duration::from_millis(500));
+        assert_eq!(LeadIn::check(&mut until, start), LeadIn::Holding);
+        assert_eq!(LeadIn::check(&mut until, start + Duration::from_millis(499)), LeadIn::Holding);
+        assert_eq!(LeadIn::check(&mut until, start + Duration::from_millis(500)), LeadIn::JustEnded);
+        assert_eq!(until, None);
+        assert_eq!(LeadIn::check(&mut until, start + Duration::from_millis(600)), LeadIn::Over);
+    }
+
+    #[test]
+    fn no_lead_in_never_holds() {
+        assert_eq!(LeadIn::check(&mut None, std::time::Instant::now()), LeadIn::Over);
+    }
+
+    #[test]
+    fn position_shows_elapsed_or_remaining() {
+        let remaining = Duration::from_secs(130);
+        assert_eq!(position_text(PositionStyle::Elapsed, ProgressBasis::Words, 142, 980, remaining), "142/980");
+        assert_eq!(position_text(PositionStyle::Elapsed, ProgressBasis::Time, 142, 980, remaining), "142/980");
+        assert_eq!(position_text(PositionStyle::Remaining, ProgressBasis::Words, 142, 980, remaining), "838 left");
+        assert_eq!(position_text(PositionStyle::Remaining, ProgressBasis::Time, 142, 980, remaining), "2:10 left");
+    }
+
+    #[test]
+    fn read_context_fades_with_distance_behind() {
+        let alphas: Vec<f32> = (1..=3).map(|distance| read_context_alpha(distance, 3, true)).collect();
+        assert!((alphas[0] - CONTEXT_ALPHA * 0.75).abs() < 1e-6);
+        assert!((alphas[2] - CONTEXT_ALPHA * 0.25).abs() < 1e-6);
+        assert!(alphas.windows(2).all(|pair| pair[0] > pair[1]));
+        assert_eq!(read_context_alpha(9, 3, true), 0.0);
+        assert_eq!(read_context_alpha(3, 3, false), CONTEXT_ALPHA);
+    }
+
+    #[test]
+    fn only_read_context_fades() {
+        let words: Vec<Word> = "one two three four five".split(' ').map(|w| Word::new(w.to_string())).collect();
+        let context = context_words(&words, 2, 2, true);
+        let preceding: Vec<&str> = context.preceding.iter().map(|(text, _)| text.as_str()).collect();
+        assert_eq!(preceding, ["one", "two"]);
+        assert!(context.preceding[0].1 < context.preceding[1].1);
+        assert!(context.following.iter().all(|(_, alpha)| *alpha == CONTEXT_ALPHA));
+    }
+
+    #[test]
+    fn context_slice_is_cut_short_at_the_text_ends() {
+        assert_eq!(context_bounds(0, 10, 3), (0..0, 1..4));
+        assert_eq!(context_bounds(1, 10, 3), (0..1, 2..5));
+        assert_eq!(context_bounds(5, 10, 3), (2..5, 6..9));
+        assert_eq!(context_bounds(9, 10, 3), (6..9, 10..10));
+        // Once finished the index is past the end; nothing follows
+        assert_eq!(context_bounds(12, 10, 3), (7..10, 10..10));
+        assert_eq!(context_bounds(4, 10, 0), (4..4, 5..5));
+    }
+
+    #[test]
+    fn progress_bar_x_maps_to_a_word_index() {
+        let track = egui::Rect::from_min_size(egui::pos2(10.0, 0.0), egui::vec2(200.0, 4.0));
+        assert_eq!(track_index(10.0, track, 100), 0);
+        assert_eq!(track_index(110.0, track, 100), 50);
+        assert_eq!(track_index(210.0, track, 100), 99);
+        // Dragging past either edge pins to the first or last word
+        assert_eq!(track_index(-50.0, track, 100), 0);
+        assert_eq!(track_index(400.0, track, 100), 99);
+        assert_eq!(track_index(110.0, track, 0), 0);
+    }
+
+    fn args(list: &[&str]) -> impl Iterator<Item = String> {
+        list.iter().map(|arg| arg.to_string()).collect::<Vec<_>>().into_iter()
+    }
+
+    #[test]
+    fn now_flag_is_parsed() {
+        assert!(LaunchOptions::parse(args(&["--now"])).read_now);
+        assert!(LaunchOptions::parse(args(&["--duration", "5m", "--now"])).read_now);
+        assert!(!LaunchOptions::parse(args(&["--duration", "5m"])).read_now);
+    }
+
+    #[test]
+    fn durations_parse_with_units_and_reject_overflow() {
+        assert_eq!(parse_duration("300"), Some(Duration::from_secs(300)));
+        assert_eq!(parse_duration("90s"), Some(Duration::from_secs(90)));
+        assert_eq!(parse_duration("5m"), Some(Duration::from_secs(300)));
+        assert_eq!(parse_duration("1h"), Some(Duration::from_secs(3600)));
+        assert_eq!(parse_duration("5d"), None);
+        assert_eq!(parse_duration("99999999999999999h"), None);
+        assert_eq!(parse_duration(&format!("{}m", u64::MAX / 2)), None);
+    }
+
+    #[test]
+    fn launch_flag_starts_exactly_one_read() {
+        let launch = LaunchOptions::parse(args(&["--now"]));
+        let flag = AtomicBool::new(launch_trigger(&Config::default(), &launch));
+        assert!(take_trigger(&flag));
+        assert!(!take_trigger(&flag));
+    }
+
+    #[test]
+    fn read_on_launch_arms_the_trigger_without_now() {
+        let launch = LaunchOptions::default();
+        let mut config = Config::default();
+        assert!(!launch_trigger(&config, &launch));
+        config.read_on_launch = true;
+        assert!(launch_trigger(&config, &launch));
+    }
+}
@@ -0,0 +1,111 @@
+use crate::config::Config;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+/// A note jotted while reading, pinned to a word of a document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Note {
+    pub document: String, // `document_id` of the text it belongs to
+    pub word_index: usize,
+    pub text: String,
+}
+
+/// All notes, persisted as `notes.json` in the app directory
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Notes {
+    notes: Vec<Note>,
+}
+
+impl Notes {
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        fs::write(Self::path()?, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    fn path() -> Result<PathBuf> {
+        Ok(Config::app_dir()?.join("notes.json"))
+    }
+
+    /// Pin `text` to word `word_index` of `document`; false if the note is blank
+    pub fn add(&mut self, document: &str, word_index: usize, text: &str) -> bool {
+        let text = text.trim();
+        if text.is_empty() {
+            return false;
+        }
+        self.notes.push(Note {
+            document: document.to_string(),
+            word_index,
+            text: text.to_string(),
+        });
+        true
+    }
+
+    /// The notes on `document`, in reading order
+    pub fn for_document(&self, document: &str) -> Vec<&Note> {
+        let mut notes: Vec<&Note> = self.notes.iter().filter(|n| n.document == document).collect();
+        notes.sort_by_key(|n| n.word_index);
+        notes
+    }
+}
+
+/// Stable identifier for a text (64-bit FNV-1a, in hex), the same across runs and builds
+pub fn document_id(text: &str) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in text.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("{:016x}", hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notes_round_trip_through_the_notes_format() {
+        let mut notes = Notes::default();
+        assert!(notes.add("doc", 12, "  check this claim "));
+        assert!(!notes.add("doc", 13, "   "));
+        let loaded: Notes = serde_json::from_str(&serde_json::to_string_pretty(&notes).unwrap()).unwrap();
+        let expected = Note {
+            document: "doc".to_string(),
+            word_index: 12,
+            text: "check this claim".to_string(),
+        };
+        assert_eq!(loaded.for_document("doc"), [&expected]);
+    }
+
+    #[test]
+    fn notes_belong_to_their_document_in_reading_order() {
+        let first = document_id("The first text.");
+        let second = document_id("The second text.");
+        let mut notes = Notes::default();
+        notes.add(&first, 40, "later");
+        notes.add(&second, 5, "elsewhere");
+        notes.add(&first, 3, "earlier");
+
+        let positions: Vec<(usize, &str)> =
+            notes.for_document(&first).iter().map(|n| (n.word_index, n.text.as_str())).collect();
+        assert_eq!(positions, [(3, "earlier"), (40, "later")]);
+        assert_eq!(notes.for_document(&second).len(), 1);
+        assert!(notes.for_document(&document_id("unseen")).is_empty());
+    }
+
+    #[test]
+    fn document_id_is_stable_and_content_based() {
+        assert_eq!(document_id(""), "cbf29ce484222325");
+        assert_eq!(document_id("same text"), document_id("same text"));
+        assert_ne!(document_id("same text"), document_id("same text."));
+    }
+}
@@ -0,0 +1,108 @@
+use crate::config::Config;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+/// Where reading of one text stopped
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedPosition {
+    pub document: String, // `notes::document_id` of the text
+    pub word_index: usize,
+}
+
+/// Places to resume recently read texts, least recently read first, persisted as
+/// `positions.json` in the app directory
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ReadingPositions {
+    entries: Vec<SavedPosition>,
+}
+
+impl ReadingPositions {
+    /// Most texts remembered; reading another forgets the one read longest ago
+    pub const MAX_ENTRIES: usize = 32;
+
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        fs::write(Self::path()?, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    fn path() -> Result<PathBuf> {
+        Ok(Config::app_dir()?.join("positions.json"))
+    }
+
+    /// Where reading of `document` stopped, if it is remembered
+    pub fn get(&self, document: &str) -> Option<usize> {
+        self.entries.iter().find(|entry| entry.document == document).map(|entry| entry.word_index)
+    }
+
+    /// Record that reading of `document` stopped at `word_index`, making it the most recent
+    pub fn remember(&mut self, document: &str, word_index: usize) {
+        self.forget(document);
+        self.entries.push(SavedPosition {
+            document: document.to_string(),
+            word_index,
+        });
+        if self.entries.len() > Self::MAX_ENTRIES {
+            self.entries.remove(0);
+        }
+    }
+
+    /// Drop the position for `document`, so it next opens at the start
+    pub fn forget(&mut self, document: &str) {
+        self.entries.retain(|entry| entry.document != document);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notes::document_id;
+    use crate::rsvp_engine::RSVPEngine;
+
+    #[test]
+    fn saved_position_restores_the_reading_spot() {
+        let text = "one two three four five six";
+        let mut positions = ReadingPositions::default();
+        positions.remember(&document_id(text), 4);
+
+        let loaded: ReadingPositions = serde_json::from_str(&serde_json::to_string(&positions).unwrap()).unwrap();
+        let mut engine = RSVPEngine::new(text, 300, 300, 0);
+        engine.seek_to(loaded.get(&document_id(text)).unwrap());
+        assert_eq!(engine.get_current_word().map(|w| w.text.as_str()), Some("five"));
+        assert_eq!(loaded.get(&document_id("another text")), None);
+    }
+
+    #[test]
+    fn remembering_again_replaces_the_position() {
+        let mut positions = ReadingPositions::default();
+        positions.remember("doc", 3);
+        positions.remember("doc", 9);
+        assert_eq!(positions.get("doc"), Some(9));
+        positions.forget("doc");
+        assert_eq!(positions.get("doc"), None);
+    }
+
+    #[test]
+    fn least_recently_read_is_evicted_past_the_cap() {
+        let mut positions = ReadingPositions::default();
+        for i in 0..ReadingPositions::MAX_ENTRIES {
+            positions.remember(&format!("doc{}", i), i);
+        }
+        // Reading doc0 again makes doc1 the oldest
+        positions.remember("doc0", 7);
+        positions.remember("new", 1);
+        assert_eq!(positions.get("doc1"), None);
+        assert_eq!(positions.get("doc0"), Some(7));
+        assert_eq!(positions.get("new"), Some(1));
+        assert_eq!(positions.entries.len(), ReadingPositions::MAX_ENTRIES);
+    }
+}
@@ -0,0 +1,588 @@
+use crate::config::{CodeCommentLang, PreprocessConfig};
+
+/// Prepare copied text for reading according to the `[preprocess]` settings
+pub fn apply(text: &str, config: &PreprocessConfig) -> String {
+    let text = if config.strip_html && looks_like_html(text) {
+        html_to_text(text)
+    } else {
+        text.to_string()
+    };
+    // Nothing matched: read everything rather than nothing
+    let text = extract_comments(&text, config.code_comment_lang).unwrap_or(text);
+    if config.strip_markdown {
+        strip_markdown(&text)
+    } else {
+        text
+    }
+}
+
+/// Markdown as the text it renders to: heading hashes, quote marks, bullets, rules and
+/// code fences dropped, `[text](url)` links and images reduced to their text, and
+/// emphasis markers and backticks removed. Line and paragraph breaks are kept.
+pub fn strip_markdown(text: &str) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    let mut in_fence = false;
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            // Code blocks are read as written
+            lines.push(line.to_string());
+            continue;
+        }
+        if is_rule(trimmed) {
+            lines.push(String::new());
+            continue;
+        }
+
+        let mut content = trimmed;
+        while let Some(rest) = content.strip_prefix('>') {
+            content = rest.trim_start();
+        }
+        let hashes = content.chars().take_while(|&c| c == '#').count();
+        if (1..=6).contains(&hashes) && content[hashes..].starts_with(' ') {
+            content = content[hashes..].trim().trim_end_matches('#').trim_end();
+        }
+        for bullet in ["- ", "* ", "+ "] {
+            if let Some(rest) = content.strip_prefix(bullet) {
+                content = rest.trim_start();
+                break;
+            }
+        }
+
+        let inline = strip_links(content).replace('`', "");
+        let words: Vec<String> = inline.split_whitespace().map(strip_emphasis).filter(|w| !w.is_empty()).collect();
+        lines.push(words.join(" "));
+    }
+
+    lines.join("\n")
+}
+
+/// A thematic break such as `---`, `***` or `_ _ _`
+fn is_rule(line: &str) -> bool {
+    let marks: String = line.chars().filter(|c| !c.is_whitespace()).collect();
+    marks.len() >= 3 && ['-', '*', '_'].iter().any(|&m| marks.chars().all(|c| c == m))
+}
+
+/// `[text](url)`, `[text][ref]` and `![alt](url)` as just their text
+fn strip_links(line: &str) -> String {
+    let mut out = String::new();
+    let mut rest = line;
+
+    while let Some(open) = rest.find('[') {
+        let image = rest[..open].ends_with('!');
+        out.push_str(&rest[..if image { open - 1 } else { open }]);
+        let after = &rest[open + 1..];
+        let Some(close) = after.find(']') else {
+            out.push_str(&rest[open..]);
+            return out;
+        };
+        let label = &after[..close];
+        let tail = &after[close + 1..];
+        let target_end = match tail.chars().next() {
+            Some('(') => tail.find(')'),
+            Some('[') => tail.find(']'),
+            _ => None,
+        };
+        match target_end {
+            Some(end) => {
+                out.push_str(label);
+                rest = &tail[end + 1..];
+            }
+            None => {
+                // Brackets that aren't a link are text
+                out.push_str(&rest[if image { open - 1 } else { open }..open + 1]);
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// A word without the `*`, `_` and `~` markers around it, keeping inner ones
+/// (`snake_case`, `2*3`) and any punctuation outside the markers
+fn strip_emphasis(word: &str) -> String {
+    let is_marker = |c: char| matches!(c, '*' | '_' | '~');
+    let is_outer = |c: char| matches!(c, '(' | ')' | '"' | '\'' | '.' | ',' | ';' | ':' | '!' | '?');
+
+    let lead_len = word.len() - word.trim_start_matches(is_outer).len();
+    let (lead, rest) = word.split_at(lead_len);
+    let rest = rest.trim_start_matches(is_marker);
+    let trail_len = rest.len() - rest.trim_end_matches(is_outer).len();
+    let (body, trail) = rest.split_at(rest.len() - trail_len);
+    let body = body.trim_end_matches(is_marker);
+
+    if body.is_empty() {
+        return String::new();
+    }
+    format!("{}{}{}", lead, body, trail)
+}
+
+/// Tags whose presence marks text as HTML, besides any closing tag
+const HTML_MARKERS: &[&str] = &["<p>", "<p ", "<br", "<div", "<span", "<a href", "<b>", "<i>", "<li>", "<!doctype"];
+
+/// Whether `text` reads as HTML markup rather than plain text that happens to hold a '<':
+/// it has a closing tag such as `</p>` or one of `HTML_MARKERS`
+pub fn looks_like_html(text: &str) -> bool {
+    let lower = text.to_lowercase();
+    let closing_tag = lower.split("</").skip(1).any(|rest| {
+        let name_len = rest.chars().take_while(char::is_ascii_alphanumeric).count();
+        name_len > 0 && rest[name_len..].starts_with('>')
+    });
+    closing_tag || HTML_MARKERS.iter().any(|marker| lower.contains(marker))
+}
+
+/// Tags that separate paragraphs
+const HTML_BLOCK_TAGS: &[&str] = &[
+    "p", "div", "h1", "h2", "h3", "h4", "h5", "h6", "blockquote", "pre", "section", "article",
+    "header", "footer", "ul", "ol", "table", "hr",
+];
+/// Tags that end a line
+const HTML_LINE_TAGS: &[&str] = &["br", "li", "tr", "dt", "dd"];
+/// Tags whose contents are code or styling, never text
+const HTML_SKIPPED_TAGS: &[&str] = &["script", "style", "head", "title", "noscript"];
+
+/// Plain text of HTML markup: tags dropped (and the contents of scripts and styles),
+/// entities decoded, whitespace collapsed as a browser would, block tags kept as
+/// paragraph breaks and `<br>`s as line breaks
+pub fn html_to_text(html: &str) -> String {
+    let mut out = String::new();
+    let mut chars = html.chars().peekable();
+    let mut skipping: Option<String> = None; // Inside this skipped tag
+
+    while let Some(c) = chars.next() {
+        let starts_tag = c == '<' && chars.peek().is_some_and(|&n| n.is_ascii_alphabetic() || matches!(n, '/' | '!' | '?'));
+        if starts_tag {
+            let mut tag = String::new();
+            let mut quote: Option<char> = None;
+            for c in chars.by_ref() {
+                match quote {
+                    Some(q) if c == q => quote = None,
+                    Some(_) => {}
+                    None if c == '"' || c == '\'' => quote = Some(c),
+                    None if c == '>' => break,
+                    None => tag.push(c),
+                }
+            }
+            let closing = tag.starts_with('/');
+            let name: String = tag
+                .trim_start_matches('/')
+                .chars()
+                .take_while(|c| c.is_ascii_alphanumeric())
+                .collect::<String>()
+                .to_ascii_lowercase();
+
+            if let Some(skipped) = &skipping {
+                if closing && name == *skipped {
+                    skipping = None;
+                }
+            } else if HTML_SKIPPED_TAGS.contains(&name.as_str()) && !closing && !tag.ends_with('/') {
+                skipping = Some(name);
+            } else if HTML_BLOCK_TAGS.contains(&name.as_str()) {
+                push_break(&mut out, 2);
+            } else if HTML_LINE_TAGS.contains(&name.as_str()) {
+                push_break(&mut out, 1);
+            }
+            continue;
+        }
+        if skipping.is_some() {
+            continue;
+        }
+
+        let c = if c == '&' { decode_entity(&mut chars).unwrap_or('&') } else { c };
+        if c.is_whitespace() {
+            if !out.is_empty() && !out.ends_with(char::is_whitespace) {
+                out.push(' ');
+            }
+        } else {
+            out.push(c);
+        }
+    }
+
+    out.trim().to_string()
+}
+
+/// End the current line, leaving `newlines` line breaks (at most) before the next text
+fn push_break(out: &mut String, newlines: usize) {
+    let trimmed = out.trim_end_matches([' ', '\t']).len();
+    out.truncate(trimmed);
+    if out.is_empty() {
+        return;
+    }
+    let existing = out.len() - out.trim_end_matches('\n').len();
+    for _ in existing..newlines {
+        out.push('\n');
+    }
+}
+
+/// The character for the entity after a '&' (its name or number up to ';'), consuming it.
+/// None, consuming nothing, when no known entity follows.
+fn decode_entity(chars: &mut std::iter::Peekable<std::str::Chars>) -> Option<char> {
+    let mut lookahead = chars.clone();
+    let mut name = String::new();
+    loop {
+        match lookahead.next()? {
+            ';' => break,
+            c if c.is_ascii_alphanumeric() || c == '#' => name.push(c),
+            _ => return None,
+        }
+        if name.len() > 10 {
+            return None;
+        }
+    }
+
+    let decoded = match name.as_str() {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => ' ',
+        "mdash" => '\u{2014}',
+        "ndash" => '\u{2013}',
+        "hellip" => '\u{2026}',
+        "lsquo" => '\u{2018}',
+        "rsquo" => '\u{2019}',
+        "ldquo" => '\u{201C}',
+        "rdquo" => '\u{201D}',
+        _ => {
+            let number = name.strip_prefix('#')?;
+            let code = match number.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => number.parse().ok()?,
+            };
+            char::from_u32(code)?
+        }
+    };
+    *chars = lookahead;
+    Some(decoded)
+}
+
+/// Pull the comments out of `text` as source code in `lang`, one comment line per output line.
+/// None when the filter is off or the text has no comments.
+pub fn extract_comments(text: &str, lang: CodeCommentLang) -> Option<String> {
+    let slash_style = match lang {
+        CodeCommentLang::Off => return None,
+        CodeCommentLang::Rust | CodeCommentLang::C | CodeCommentLang::JavaScript => true,
+        CodeCommentLang::Python | CodeCommentLang::Shell => false,
+    };
+    // Single quotes are lifetimes and char literals in C-like code, strings elsewhere
+    let quotes: &[char] = if slash_style { &['"'] } else { &['"', '\''] };
+
+    let mut lines: Vec<String> = Vec::new();
+    let mut chars = text.chars().peekable();
+    let mut quote: Option<char> = None;
+
+    while let Some(c) = chars.next() {
+        if let Some(q) = quote {
+            if c == '\\' {
+                chars.next();
+            } else if c == q || c == '\n' {
+                quote = None;
+            }
+            continue;
+        }
+
+        let line_comment = if slash_style {
+            c == '/' && chars.peek() == Some(&'/')
+        } else {
+            c == '#'
+        };
+        let block_comment = slash_style && c == '/' && chars.peek() == Some(&'*');
+
+        if line_comment {
+            let body: String = chars.by_ref().take_while(|&c| c != '\n').collect();
+            push_line(&mut lines, body.trim_start_matches(['/', '!', '#']));
+        } else if block_comment {
+            chars.next();
+            let mut body = String::new();
+            while let Some(c) = chars.next() {
+                if c == '*' && chars.peek() == Some(&'/') {
+                    chars.next();
+                    break;
+                }
+                body.push(c);
+            }
+            for line in body.lines() {
+                push_line(&mut lines, line.trim().trim_start_matches(['*', '!']));
+            }
+        } else if quotes.contains(&c) {
+            quote = Some(c);
+        }
+    }
+
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+fn push_line(lines: &mut Vec<String>, line: &str) {
+    let line = line.trim();
+    if !line.is_empty() {
+        lines.push(line.to_string());
+    }
+}
+
+/// RTF groups whose contents are settings rather than document text
+const RTF_SKIPPED_DESTINATIONS: &[&str] = &[
+    "fonttbl", "colortbl", "stylesheet", "info", "pict", "header", "footer", "listtable",
+    "listoverridetable", "generator", "filetbl", "rsidtbl", "xmlnstbl", "themedata",
+];
+
+/// Plain text of an RTF document: control words and formatting groups dropped,
+/// paragraph breaks, escapes and Unicode characters kept
+pub fn rtf_to_text(rtf: &str) -> String {
+    let mut out = String::new();
+    let mut chars = rtf.chars().peekable();
+    let mut skip_stack: Vec<bool> = Vec::new();
+    let mut skipping = false;
+    let mut unicode_fallback = 1; // \ucN: characters following \u that stand in for it
+    let mut pending_fallback = 0; // Fallback characters still to drop
+
+    // Emit a character unless inside a skipped group or dropping \u fallbacks
+    let emit = |c: char, out: &mut String, skipping: bool, pending_fallback: &mut usize| {
+        if *pending_fallback > 0 {
+            *pending_fallback -= 1;
+        } else if !skipping {
+            out.push(c);
+        }
+    };
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => {
+                skip_stack.push(skipping);
+            }
+            '}' => {
+                skipping = skip_stack.pop().unwrap_or(false);
+            }
+            '\r' | '\n' => {}
+            '\\' => match chars.peek().copied() {
+                Some(c @ ('\\' | '{' | '}')) => {
+                    chars.next();
+                    emit(c, &mut out, skipping, &mut pending_fallback);
+                }
+                Some('\n' | '\r') => {
+                    // An escaped line break is a paragraph break (Cocoa writes these)
+                    chars.next();
+                    emit('\n', &mut out, skipping, &mut pending_fallback);
+                }
+                Some('~') => {
+                    chars.next();
+                    emit(' ', &mut out, skipping, &mut pending_fallback);
+                }
+                Some('_') => {
+                    chars.next();
+                    emit('-', &mut out, skipping, &mut pending_fallback);
+                }
+                Some('*') => {
+                    // Optional destination: ignorable by readers that don't know it
+                    chars.next();
+                    skipping = true;
+                }
+                Some('\'') => {
+                    chars.next();
+                    let hex: String = chars.by_ref().take(2).collect();
+                    if let Ok(byte) = u8::from_str_radix(&hex, 16) {
+                        emit(cp1252_char(byte), &mut out, skipping, &mut pending_fallback);
+                    }
+                }
+                Some(c) if c.is_ascii_alphabetic() => {
+                    let mut word = String::new();
+                    while let Some(&c) = chars.peek().filter(|c| c.is_ascii_alphabetic()) {
+                        word.push(c);
+                        chars.next();
+                    }
+                    let mut param = String::new();
+                    if chars.peek() == Some(&'-') {
+                        param.push('-');
+                        chars.next();
+                    }
+                    while let Some(&c) = chars.peek().filter(|c| c.is_ascii_digit()) {
+                        param.push(c);
+                        chars.next();
+                    }
+                    // A single space ends the control word and is not text
+                    if chars.peek() == Some(&' ') {
+                        chars.next();
+                    }
+                    let param: Option<i32> = param.parse().ok();
+
+                    let text = match word.as_str() {
+                        "par" | "line" | "sect" | "page" => Some('\n'),
+                        "tab" => Some('\t'),
+                        "emdash" => Some('\u{2014}'),
+                        "endash" => Some('\u{2013}'),
+                        "lquote" => Some('\u{2018}'),
+                        "rquote" => Some('\u{2019}'),
+                        "ldblquote" => Some('\u{201C}'),
+                        "rdblquote" => Some('\u{201D}'),
+                        "bullet" => Some('\u{2022}'),
+                        "u" => {
+                            // Negative values wrap to code points above 0x7FFF
+                            let code = param.map(|n| if n < 0 { n + 65536 } else { n });
+                            let c = code.and_then(|n| char::from_u32(n as u32));
+                            if let Some(c) = c {
+                                emit(c, &mut out, skipping, &mut pending_fallback);
+                            }
+                            pending_fallback = unicode_fallback;
+                            None
+                        }
+                        "uc" => {
+                            unicode_fallback = param.unwrap_or(1).max(0) as usize;
+                            None
+                        }
+                        word if RTF_SKIPPED_DESTINATIONS.contains(&word) => {
+                            skipping = true;
+                            None
+                        }
+                        _ => None,
+                    };
+                    if let Some(c) = text {
+                        emit(c, &mut out, skipping, &mut pending_fallback);
+                    }
+                }
+                _ => {}
+            },
+            c => emit(c, &mut out, skipping, &mut pending_fallback),
+        }
+    }
+
+    out.trim().to_string()
+}
+
+/// Character for a `\'hh` escape, which RTF from macOS and Windows writes in Windows-1252
+fn cp1252_char(byte: u8) -> char {
+    const HIGH: [char; 32] = [
+        '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8D}', 'Ž', '\u{8F}',
+        '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9D}', 'ž', 'Ÿ',
+    ];
+    match byte {
+        0x80..=0x9F => HIGH[(byte - 0x80) as usize],
+        _ => byte as char,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rust_comments_are_extracted() {
+        let code = r#"/// Adds one.
+fn inc(x: i32) -> i32 {
+    let url = "http://example.com"; // keep URLs in strings out
+    /* Block comments
+     * span lines */
+    x + 1
+}"#;
+        assert_eq!(
+            extract_comments(code, CodeCommentLang::Rust).as_deref(),
+            Some("Adds one.\nkeep URLs in strings out\nBlock comments\nspan lines")
+        );
+    }
+
+    #[test]
+    fn python_comments_are_extracted() {
+        let code = "# Greets someone.\ndef greet(name):\n    print('#1 fan', name)  # not the string\n";
+        assert_eq!(
+            extract_comments(code, CodeCommentLang::Python).as_deref(),
+            Some("Greets someone.\nnot the string")
+        );
+    }
+
+    #[test]
+    fn code_without_comments_reads_everything() {
+        let config = PreprocessConfig { code_comment_lang: CodeCommentLang::Python, ..PreprocessConfig::default() };
+        assert_eq!(extract_comments("x = 1\n", CodeCommentLang::Python), None);
+        assert_eq!(apply("x = 1\n", &config), "x = 1\n");
+        assert_eq!(extract_comments("// a comment", CodeCommentLang::Off), None);
+    }
+
+    #[test]
+    fn rtf_sample_becomes_plain_text() {
+        let rtf = r"{\rtf1\ansi\ansicpg1252\cocoartf2709
+{\fonttbl\f0\fswiss\fcharset0 Helvetica;}
+{\colortbl;\red255\green255\blue255;}
+{\*\expandedcolortbl;;}
+\pard\tx566\pardirnatural\partightenfactor0
+
+\f0\fs24 \cf0 Hello, \b bold\b0  world.\
+Caf\'e9 \'97 done \{ok\}.}";
+        assert_eq!(rtf_to_text(rtf), "Hello, bold world.\nCafé — done {ok}.");
+    }
+
+    #[test]
+    fn rtf_paragraphs_quotes_and_unicode_escapes_survive() {
+        let rtf = r"{\rtf1 First\par Second \ldblquote quoted\rdblquote\tab end \uc1\u-3913?\u1605?}";
+        assert_eq!(rtf_to_text(rtf), "First\nSecond \u{201C}quoted\u{201D}\tend \u{F0B7}\u{645}");
+    }
+
+    fn words(text: &str) -> Vec<&str> {
+        text.split_whitespace().collect()
+    }
+
+    #[test]
+    fn html_tags_and_entities_are_stripped() {
+        let html = "<p>Hello&nbsp;<b>world</b></p>";
+        assert!(looks_like_html(html));
+        assert_eq!(words(&html_to_text(html)), ["Hello", "world"]);
+        assert_eq!(html_to_text("<p>Fish &amp; chips &lt;3</p><script>var x = 1;</script>").trim(), "Fish & chips <3");
+    }
+
+    #[test]
+    fn plain_text_with_angle_brackets_is_not_html() {
+        assert!(!looks_like_html("if a < b and c > d then"));
+        assert!(!looks_like_html("x</y"));
+        assert!(looks_like_html("line one<br>line two"));
+    }
+
+    #[test]
+    fn html_stripping_follows_the_config_flag() {
+        let html = "<p>Hello&nbsp;<b>world</b></p>";
+        let config = PreprocessConfig::default();
+        assert!(config.strip_html);
+        assert_eq!(words(&apply(html, &config)), ["Hello", "world"]);
+        let off = PreprocessConfig { strip_html: false, ..config };
+        assert_eq!(apply(html, &off), html);
+    }
+
+    #[test]
+    fn markdown_syntax_is_stripped() {
+        let markdown = "# Getting started\n\nThis is **really** simple.\n\n- first item\n* second item\n\nSee [the docs](https://example.com/docs) and run `cargo build`.";
+        let cleaned = strip_markdown(markdown);
+        let lines: Vec<&str> = cleaned.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                "Getting started",
+                "",
+                "This is really simple.",
+                "",
+                "first item",
+                "second item",
+                "",
+                "See the docs and run cargo build.",
+            ]
+        );
+    }
+
+    #[test]
+    fn markdown_keeps_inner_markers_and_fenced_code() {
+        assert_eq!(strip_markdown("snake_case and 2*3 stay, _this_ goes"), "snake_case and 2*3 stay, this goes");
+        assert_eq!(strip_markdown("```\nlet x = *y;\n```"), "let x = *y;");
+        assert_eq!(strip_markdown("#hashtag is not a heading"), "#hashtag is not a heading");
+    }
+
+    #[test]
+    fn markdown_stripping_follows_the_config_flag() {
+        let markdown = "**bold** [link](url)";
+        let on = PreprocessConfig { strip_markdown: true, ..PreprocessConfig::default() };
+        assert_eq!(words(&apply(markdown, &on)), ["bold", "link"]);
+        let off = PreprocessConfig { strip_markdown: false, ..PreprocessConfig::default() };
+        assert_eq!(apply(markdown, &off), markdown);
+    }
+}
@@ -0,0 +1,148 @@
+use crate::config::Config;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs;
+use std::path::PathBuf;
+
+/// A text saved for later reading
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueItem {
+    pub title: String, // First few words, for display
+    pub text: String,
+}
+
+/// Read-later list, oldest first, persisted as `queue.json` in the app directory
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ReadingQueue {
+    items: VecDeque<QueueItem>,
+}
+
+impl ReadingQueue {
+    /// Most items kept; further texts are refused until some are read
+    pub const MAX_ITEMS: usize = 50;
+    const TITLE_WORDS: usize = 6;
+
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        fs::write(Self::path()?, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    fn path() -> Result<PathBuf> {
+        Ok(Config::app_dir()?.join("queue.json"))
+    }
+
+    /// Add `text` to the end of the queue; false if it is blank or the queue is full
+    pub fn enqueue(&mut self, text: &str) -> bool {
+        if text.trim().is_empty() || self.items.len() >= Self::MAX_ITEMS {
+            return false;
+        }
+        self.items.push_back(QueueItem {
+            title: Self::title_for(text),
+            text: text.to_string(),
+        });
+        true
+    }
+
+    /// The next item to read, left in place until `dequeue`
+    pub fn front(&self) -> Option<&QueueItem> {
+        self.items.front()
+    }
+
+    /// Remove the next item (once it has been read)
+    pub fn dequeue(&mut self) -> Option<QueueItem> {
+        self.items.pop_front()
+    }
+
+    /// Drop the item whose read just finished and return the text to read next
+    pub fn next_after_finish(&mut self) -> Option<String> {
+        self.items.pop_front();
+        self.items.front().map(|item| item.text.clone())
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    fn title_for(text: &str) -> String {
+        let words: Vec<&str> = text.split_whitespace().collect();
+        let mut title = words[..words.len().min(Self::TITLE_WORDS)].join(" ");
+        if words.len() > Self::TITLE_WORDS {
+            title.push('…');
+        }
+        title
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enqueue_appends_to_the_end() {
+        let mut queue = ReadingQueue::default();
+        assert!(queue.enqueue("first text"));
+        assert!(queue.enqueue("second text"));
+        assert!(!queue.enqueue("   "));
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.front().unwrap().text, "first text");
+    }
+
+    #[test]
+    fn dequeue_removes_oldest_first() {
+        let mut queue = ReadingQueue::default();
+        queue.enqueue("one");
+        queue.enqueue("two");
+        queue.enqueue("three");
+        assert_eq!(queue.dequeue().unwrap().text, "one");
+        assert_eq!(queue.dequeue().unwrap().text, "two");
+        assert_eq!(queue.dequeue().unwrap().text, "three");
+        assert!(queue.dequeue().is_none());
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn full_queue_refuses_newer_texts() {
+        let mut queue = ReadingQueue::default();
+        for i in 0..ReadingQueue::MAX_ITEMS {
+            assert!(queue.enqueue(&format!("text {}", i)));
+        }
+        assert!(!queue.enqueue("one too many"));
+        assert_eq!(queue.len(), ReadingQueue::MAX_ITEMS);
+        assert_eq!(queue.front().unwrap().text, "text 0");
+        let last = std::iter::from_fn(|| queue.dequeue()).last().unwrap();
+        assert_eq!(last.text, format!("text {}", ReadingQueue::MAX_ITEMS - 1));
+    }
+
+    #[test]
+    fn title_is_cut_to_six_words() {
+        let mut queue = ReadingQueue::default();
+        queue.enqueue("one two three four five six seven eight");
+        queue.enqueue("just  three\nwords");
+        assert_eq!(queue.dequeue().unwrap().title, "one two three four five six…");
+        assert_eq!(queue.dequeue().unwrap().title, "just three words");
+    }
+
+    #[test]
+    fn finishing_an_item_removes_it_and_starts_the_next() {
+        let mut queue = ReadingQueue::default();
+        queue.enqueue("first text");
+        queue.enqueue("second text");
+        assert_eq!(queue.next_after_finish().as_deref(), Some("second text"));
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue.next_after_finish(), None);
+        assert!(queue.is_empty());
+    }
+}
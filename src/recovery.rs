@@ -0,0 +1,57 @@
+use crate::config::Config;
+use crate::rsvp_engine::EngineState;
+use anyhow::Result;
+use std::fs;
+use std::path::PathBuf;
+
+fn path() -> Result<PathBuf> {
+    Ok(Config::app_dir()?.join("recovery.json"))
+}
+
+/// Keep a snapshot of the read in progress so a crash doesn't lose it
+pub fn save(state: &EngineState) -> Result<()> {
+    fs::write(path()?, serde_json::to_string(state)?)?;
+    Ok(())
+}
+
+/// The snapshot left by an unfinished session, if any
+pub fn load() -> Result<Option<EngineState>> {
+    let path = path()?;
+    if !path.exists() {
+        return Ok(None);
+    }
+    Ok(Some(serde_json::from_str(&fs::read_to_string(path)?)?))
+}
+
+/// Remove the snapshot once reading ends normally
+pub fn clear() -> Result<()> {
+    let path = path()?;
+    if path.exists() {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rsvp_engine::{BreakKind, EngineState, RSVPEngine};
+
+    #[test]
+    fn engine_state_round_trips_through_the_recovery_format() {
+        let mut engine = RSVPEngine::builder("First line\nآخر word. Then more, and more.").target_wpm(480).chunk_size(2).build();
+        engine.seek_to(2);
+        let json = serde_json::to_string(&engine.state()).unwrap();
+        let state: EngineState = serde_json::from_str(&json).unwrap();
+
+        let restored = RSVPEngine::builder("").restore(state).build();
+        assert_eq!(restored.get_current_index(), 2);
+        assert_eq!(restored.get_target_wpm(), 480);
+        assert_eq!(restored.total_words(), engine.total_words());
+        for (a, b) in restored.words().iter().zip(engine.words()) {
+            assert_eq!((&a.text, a.orp_index, a.break_after, a.direction), (&b.text, b.orp_index, b.break_after, b.direction));
+            assert_eq!(a.word_count(), b.word_count());
+        }
+        assert_eq!(restored.words()[0].break_after, BreakKind::Line);
+        assert_eq!(restored.estimated_remaining(), engine.estimated_remaining());
+    }
+}
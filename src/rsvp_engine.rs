@@ -1,109 +1,778 @@
+use crate::config::{DisplayConfig, TimingConfig, TimingMode};
+use crate::language::TextDirection;
+use crate::tokenizer::{chunk_words, mark_headings, merge_spaced_emphasis, tokenize, Tokenization};
+use serde::{Deserialize, Serialize};
 use std::time::{Duration, Instant};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Word {
     pub text: String,
     pub orp_index: usize,
+    pub break_after: BreakKind, // Line structure of the source text right after this word
+    pub heading: bool,          // Part of an all-caps line (only set when heading detection is on)
+    pub emphasis: bool,         // Rebuilt from letter-spaced emphasis such as "N O T"
+    pub direction: TextDirection, // From the word's own script, so mixed-script text lays out per word
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parts: Vec<Word>, // The words flashed together as this chunk (empty for a single word)
+}
+
+/// What separates a word from the next one in the source text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum BreakKind {
+    #[default]
+    None,      // Plain spaces
+    Line,      // A single line break
+    Paragraph, // A blank line or more
+}
+
+/// Broad category of a word, shown through the color of its focus letter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WordKind {
+    Normal,
+    Number,      // "42", "3.14", "1,000", "12:30"
+    SentenceEnd, // Ends in a sentence terminator
+    Heading,     // Part of a detected heading line
+}
+
+impl WordKind {
+    pub const ALL: [WordKind; 4] = [
+        WordKind::Normal,
+        WordKind::Number,
+        WordKind::SentenceEnd,
+        WordKind::Heading,
+    ];
+
+    /// Name shown in the settings window
+    pub fn label(self) -> &'static str {
+        match self {
+            WordKind::Normal => "Normal",
+            WordKind::Number => "Numbers",
+            WordKind::SentenceEnd => "Sentence ends",
+            WordKind::Heading => "Headings",
+        }
+    }
 }
 
 impl Word {
     pub fn new(text: String) -> Self {
         let orp_index = Self::calculate_orp(&text);
-        Self { text, orp_index }
+        let direction = TextDirection::of_text(&text);
+        Self {
+            text,
+            orp_index,
+            break_after: BreakKind::None,
+            heading: false,
+            emphasis: false,
+            direction,
+            parts: Vec::new(),
+        }
     }
 
+    /// Focus position in characters (not bytes), so accented letters and emoji count once
     fn calculate_orp(text: &str) -> usize {
-        let len = text.len();
+        let len = text.chars().count();
         match len {
             1..=3 => 0,
             4..=5 => 1,
             6..=9 => 2,
             10..=13 => 3,
-            _ => 4.min(len - 1),
+            _ => 4.min(len.saturating_sub(1)),
         }
     }
 
-    pub fn get_parts(&self) -> (String, char, String) {
+    /// Whether this word closes a sentence, i.e. ends in one of `terminators`
+    /// (trailing quotes and brackets ignored)
+    pub fn is_sentence_end(&self, terminators: &str) -> bool {
+        self.text
+            .trim_end_matches(['"', '\'', ')', ']', '\u{201D}', '\u{2019}'])
+            .ends_with(|c| terminators.contains(c))
+    }
+
+    /// Put the focus character `position` of the way into the word (0.0 = first, 1.0 = last)
+    /// instead of using the length table
+    pub fn set_orp_position(&mut self, position: f32) {
+        let len = self.text.chars().count();
+        let index = (len as f32 * position).round() as usize;
+        self.orp_index = index.min(len.saturating_sub(1));
+    }
+
+    /// Source words this stands for: those of a chunk, or just itself
+    pub fn word_count(&self) -> usize {
+        self.parts.len().max(1)
+    }
+
+    /// What kind of word this is. Headings win over sentence ends, which win over numbers.
+    pub fn kind(&self, terminators: &str) -> WordKind {
+        if self.heading {
+            WordKind::Heading
+        } else if self.is_sentence_end(terminators) {
+            WordKind::SentenceEnd
+        } else if self.is_number() {
+            WordKind::Number
+        } else {
+            WordKind::Normal
+        }
+    }
+
+    /// Emoji or symbols only ("✅", "→", "€"), with no letters, digits or ASCII
+    /// punctuation. Such tokens are shown whole, without a focus letter.
+    pub fn is_symbol(&self) -> bool {
+        !self.text.is_empty()
+            && self.text.chars().all(|c| !c.is_alphanumeric() && !c.is_ascii() && !is_wide_punctuation(c))
+    }
+
+    /// Digits with at most separators in between, ignoring surrounding punctuation ("$5", "(42)")
+    fn is_number(&self) -> bool {
+        let core = self.text.trim_matches(|c: char| !c.is_alphanumeric());
+        core.chars().any(|c| c.is_ascii_digit())
+            && core.chars().all(|c| c.is_ascii_digit() || matches!(c, '.' | ',' | ':' | '/' | '-'))
+    }
+
+    /// The focus character alone, matching the middle of `get_parts`. None for an empty word.
+    pub fn orp_char(&self) -> Option<char> {
+        self.text.chars().nth(self.orp_index).or_else(|| self.text.chars().last())
+    }
+
+    /// Split into the text before the focus character, the focus character and the
+    /// text after it. None for an empty word, which has nothing to show.
+    /// Pure: depends only on the word, so external renderers can call it freely.
+    pub fn get_parts(&self) -> Option<(String, char, String)> {
         let chars: Vec<char> = self.text.chars().collect();
+        let orp_index = self.orp_index.min(chars.len().checked_sub(1)?);
+
+        let before = chars[..orp_index].iter().collect();
+        let focus = chars[orp_index];
+        let after = chars[orp_index + 1..].iter().collect();
+
+        Some((before, focus, after))
+    }
+}
 
-        if self.orp_index >= chars.len() {
-            return (self.text.clone(), ' ', String::new());
+/// Non-ASCII punctuation (dashes, curly quotes, ellipses, «», ¿, CJK and fullwidth
+/// marks), which is text rather than a symbol
+fn is_wide_punctuation(c: char) -> bool {
+    matches!(
+        c,
+        '¡' | '§' | '«' | '¶' | '·' | '»' | '¿'
+            | '\u{2010}'..='\u{2027}'
+            | '\u{2030}'..='\u{203B}'
+            | '\u{3000}'..='\u{303F}'
+            | '\u{FF01}'..='\u{FF0F}'
+            | '\u{FF1A}'..='\u{FF20}'
+    )
+}
+
+/// Consecutive terminators beyond this add no further pause
+const MAX_TERMINATOR_RUN: usize = 3;
+
+/// Length of the longest run of consecutive characters matching `pred`
+fn longest_run(text: &str, pred: impl Fn(char) -> bool) -> usize {
+    let (mut longest, mut current) = (0, 0);
+    for c in text.chars() {
+        current = if pred(c) { current + 1 } else { 0 };
+        longest = longest.max(current);
+    }
+    longest
+}
+
+/// Rough English syllable count: vowel groups, less a silent final "e", plus an "-ing"
+/// run into the vowels before it ("being", "queueing"), at least one
+fn syllable_count(text: &str) -> usize {
+    let letters: Vec<char> = text.chars().filter(|c| c.is_alphabetic()).flat_map(char::to_lowercase).collect();
+    let is_vowel = |c: char| "aeiouy".contains(c);
+
+    let mut count = 0;
+    let mut in_vowels = false;
+    for &c in &letters {
+        if is_vowel(c) && !in_vowels {
+            count += 1;
         }
+        in_vowels = is_vowel(c);
+    }
+    let silent_e = letters.len() > 2 && letters.ends_with(&['e']) && !letters.ends_with(&['l', 'e']);
+    if silent_e && count > 1 {
+        count -= 1;
+    }
+    let n = letters.len();
+    if n > 4 && letters.ends_with(&['i', 'n', 'g']) && is_vowel(letters[n - 4]) {
+        count += 1;
+    }
+    count.max(1)
+}
+
+/// Decides how long each word stays on screen. Playback, seeking, remaining time and
+/// pause cues all go through the engine's strategy (the engine then holds the result
+/// within its display limits). Each `TimingMode` is one of these.
+pub trait TimingStrategy {
+    /// Time for a word's text at `wpm`
+    fn duration(&self, word: &str, wpm: u32) -> Duration;
+
+    /// Time for `word` where it stands in the text. Strategies that pause for
+    /// punctuation, layout or `[timing]` settings override this; by default it is
+    /// `duration` of the word's text.
+    fn word_duration(&self, word: &Word, wpm: u32, _timing: &TimingConfig) -> Duration {
+        self.duration(&word.text, wpm)
+    }
+}
 
-        let before = chars[..self.orp_index].iter().collect();
-        let focus = chars[self.orp_index];
-        let after = chars[self.orp_index + 1..].iter().collect();
+/// `strategy`'s time for `text` on its own, under the default `[timing]` settings
+fn lone_word_time(strategy: &dyn TimingStrategy, text: &str, wpm: u32) -> Duration {
+    strategy.word_duration(&Word::new(text.to_string()), wpm, &TimingConfig::default())
+}
+
+/// The usual pacing (`TimingMode::CharLength`): longer words get gently more time, and
+/// punctuation and layout pause as set in `[timing]`
+pub struct DefaultTiming;
+
+impl TimingStrategy for DefaultTiming {
+    fn duration(&self, word: &str, wpm: u32) -> Duration {
+        lone_word_time(self, word, wpm)
+    }
 
-        (before, focus, after)
+    fn word_duration(&self, word: &Word, wpm: u32, timing: &TimingConfig) -> Duration {
+        paced_time(word, wpm, timing, &|word| (1.0 + (word.text.chars().count() as f32 - 5.0) * 0.03).max(0.8))
+    }
+}
+
+/// The same time for every word at `wpm`, ignoring length, punctuation and layout
+/// (`TimingMode::Constant`)
+pub struct ConstantTiming;
+
+impl TimingStrategy for ConstantTiming {
+    fn duration(&self, _word: &str, wpm: u32) -> Duration {
+        Duration::from_secs_f32(60.0 / wpm.max(1) as f32)
+    }
+
+    fn word_duration(&self, word: &Word, wpm: u32, _timing: &TimingConfig) -> Duration {
+        // A chunk still takes as long as its words one after another
+        self.duration(&word.text, wpm) * word.word_count() as u32
+    }
+}
+
+/// Time per character (`TimingMode::Cpm`); an average word is about five, so plain
+/// prose keeps its pace. Punctuation and layout pause as usual.
+pub struct CpmTiming;
+
+impl TimingStrategy for CpmTiming {
+    fn duration(&self, word: &str, wpm: u32) -> Duration {
+        lone_word_time(self, word, wpm)
+    }
+
+    fn word_duration(&self, word: &Word, wpm: u32, timing: &TimingConfig) -> Duration {
+        paced_time(word, wpm, timing, &|word| (word.text.chars().count() as f32 / 5.0).max(0.5))
+    }
+}
+
+/// Time grows by `PER_SYLLABLE` with each (estimated) syllable past the first
+/// (`TimingMode::Syllables`); punctuation and layout pause as usual
+pub struct SyllableTiming;
+
+impl SyllableTiming {
+    const PER_SYLLABLE: f32 = 0.08;
+}
+
+impl TimingStrategy for SyllableTiming {
+    fn duration(&self, word: &str, wpm: u32) -> Duration {
+        lone_word_time(self, word, wpm)
+    }
+
+    fn word_duration(&self, word: &Word, wpm: u32, timing: &TimingConfig) -> Duration {
+        paced_time(word, wpm, timing, &|word| {
+            1.0 + Self::PER_SYLLABLE * (syllable_count(&word.text) - 1) as f32
+        })
+    }
+}
+
+/// The strategy behind a timing mode
+pub fn strategy_for(mode: TimingMode) -> Box<dyn TimingStrategy> {
+    match mode {
+        TimingMode::Constant => Box::new(ConstantTiming),
+        TimingMode::CharLength => Box::new(DefaultTiming),
+        TimingMode::Syllables => Box::new(SyllableTiming),
+        TimingMode::Cpm => Box::new(CpmTiming),
+    }
+}
+
+/// How long `word` stays on screen at `wpm` under `timing.mode`
+pub fn word_display_time(word: &Word, wpm: u32, timing: &TimingConfig) -> Duration {
+    strategy_for(timing.mode).word_duration(word, wpm, timing)
+}
+
+/// Base time at `wpm`, scaled by `length_factor` and the punctuation and layout pauses
+fn paced_time(word: &Word, wpm: u32, timing: &TimingConfig, length_factor: &dyn Fn(&Word) -> f32) -> Duration {
+    if !word.parts.is_empty() {
+        // A chunk stays up as long as its words would one after another
+        return word.parts.iter().map(|part| paced_time(part, wpm, timing, length_factor)).sum();
+    }
+    if timing.symbol_dwell_ms > 0 && word.is_symbol() {
+        return Duration::from_millis(timing.symbol_dwell_ms);
+    }
+    // A zero rate would mean an infinite dwell
+    let base_duration = Duration::from_secs_f32(60.0 / wpm.max(1) as f32);
+    let length_factor = length_factor(word);
+    let terminator_run = longest_run(&word.text, |c| timing.sentence_terminators.contains(c));
+    let punctuation_factor = if terminator_run > 0 {
+        // "What?!" and "Wait..." pause longer than a lone period
+        let extra = terminator_run.min(MAX_TERMINATOR_RUN) - 1;
+        timing.sentence_pause_factor + timing.terminator_run_bonus * extra as f32
+    } else if word.text.contains(',') {
+        timing.comma_pause_factor
+    } else {
+        1.0
+    };
+    let break_factor = match word.break_after {
+        BreakKind::Paragraph => timing.paragraph_pause_factor,
+        BreakKind::Line if timing.respect_line_breaks => timing.line_break_pause_factor,
+        _ => 1.0,
+    };
+    let heading_factor = if word.heading { timing.heading_factor } else { 1.0 };
+    let emphasis_factor = if word.emphasis { timing.emphasis_factor } else { 1.0 };
+    let factor = length_factor * punctuation_factor * break_factor * heading_factor * emphasis_factor;
+    // However the factors stack up, no single word stalls reading for long
+    // (and a bad factor that slipped past the config clamps can't make it negative)
+    base_duration.mul_f32(factor.min(timing.max_pause_factor).max(0.0))
+}
+
+/// How text becomes displayed words, shared by a new read and text appended to one
+#[derive(Debug, Clone, Copy)]
+struct WordOptions {
+    tokenization: Tokenization,
+    orp_position: Option<f32>,
+    chunk_size: usize,
+    max_word_chars: usize,
+}
+
+/// Speed `progress` (0.0–1.0) of the way from `from` to `to`
+fn interpolate_wpm(from: u32, to: u32, progress: f32) -> u32 {
+    let (from, to) = (from as f32, to as f32);
+    (from + (to - from) * progress) as u32
+}
+
+/// Split `text` into words and apply the structure, chunking and focus options
+fn prepare_words(text: &str, options: WordOptions, timing: &TimingConfig) -> Vec<Word> {
+    let mut words = tokenize(text, options.tokenization, options.max_word_chars);
+    if timing.detect_spaced_emphasis {
+        words = merge_spaced_emphasis(words);
+    }
+    if timing.detect_headings {
+        mark_headings(&mut words);
+    }
+    if options.chunk_size > 1 {
+        // Characters of CJK text run together; words are joined by spaces
+        let joiner = match options.tokenization {
+            Tokenization::Words => " ",
+            Tokenization::Characters => "",
+        };
+        words = chunk_words(words, options.chunk_size, &timing.sentence_terminators, joiner);
+    }
+    if let Some(position) = options.orp_position {
+        for word in &mut words {
+            word.set_orp_position(position);
+        }
+    }
+    words
+}
+
+/// The parts of a reading session worth keeping across a restart
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EngineState {
+    pub words: Vec<Word>,
+    pub current_index: usize,
+    pub target_wpm: u32,
+}
+
+/// Fluent construction for `RSVPEngine`; unset options keep the defaults below
+pub struct RSVPEngineBuilder<'a> {
+    text: &'a str,
+    tokenization: Tokenization,
+    start_wpm: u32,
+    target_wpm: u32,
+    warmup_words: u32,
+    wpm_range: (u32, u32),
+    end_hold: Duration,
+    stride: u32,
+    checkpoint_sentences: u32,
+    min_visible: Duration,
+    min_display: Duration,
+    max_display: Duration,
+    timing: TimingConfig,
+    strategy: Option<Box<dyn TimingStrategy>>,
+    seek_keeps_phase: bool,
+    smooth_changes: bool,
+    rewind_on_resume: usize,
+    orp_position: Option<f32>,
+    chunk_size: usize,
+    max_word_chars: usize,
+    review_words: usize,
+    restored: Option<EngineState>,
+}
+
+impl<'a> RSVPEngineBuilder<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            tokenization: Tokenization::Words,
+            start_wpm: 300,
+            target_wpm: 400,
+            warmup_words: 10,
+            wpm_range: (RSVPEngine::MIN_WPM, RSVPEngine::MAX_WPM),
+            end_hold: Duration::ZERO,
+            stride: 1,
+            checkpoint_sentences: 0,
+            min_visible: Duration::ZERO,
+            min_display: Duration::ZERO,
+            max_display: Duration::MAX,
+            timing: TimingConfig::default(),
+            strategy: None,
+            seek_keeps_phase: false,
+            smooth_changes: false,
+            rewind_on_resume: 0,
+            orp_position: None,
+            chunk_size: 1,
+            max_word_chars: DisplayConfig::default().max_word_chars,
+            review_words: 0,
+            restored: None,
+        }
+    }
+
+    /// With `Tokenization::Characters` the rates are per character rather than per word
+    pub fn tokenization(mut self, tokenization: Tokenization) -> Self {
+        self.tokenization = tokenization;
+        self
+    }
+
+    pub fn start_wpm(mut self, wpm: u32) -> Self {
+        self.start_wpm = wpm;
+        self
+    }
+
+    pub fn target_wpm(mut self, wpm: u32) -> Self {
+        self.target_wpm = wpm;
+        self
+    }
+
+    pub fn warmup_words(mut self, words: u32) -> Self {
+        self.warmup_words = words;
+        self
+    }
+
+    /// Speed changes (keys, presets, time-boxing) stay between `min` and `max`
+    /// (within `LOWEST_WPM`–`HIGHEST_WPM`)
+    pub fn wpm_range(mut self, min: u32, max: u32) -> Self {
+        let min = min.clamp(RSVPEngine::LOWEST_WPM, RSVPEngine::HIGHEST_WPM);
+        self.wpm_range = (min, max.clamp(min, RSVPEngine::HIGHEST_WPM));
+        self
+    }
+
+    /// Keep the final word on screen for `hold` before reporting finished
+    pub fn end_hold(mut self, hold: Duration) -> Self {
+        self.end_hold = hold;
+        self
+    }
+
+    pub fn stride(mut self, stride: u32) -> Self {
+        self.stride = stride;
+        self
+    }
+
+    /// Pause after every `sentences` sentences for a comprehension check (0 disables)
+    pub fn checkpoint_sentences(mut self, sentences: u32) -> Self {
+        self.checkpoint_sentences = sentences;
+        self
+    }
+
+    /// Every word stays up at least this long once shown, however it got there
+    pub fn min_visible(mut self, min_visible: Duration) -> Self {
+        self.min_visible = min_visible;
+        self
+    }
+
+    /// Keep every word's display time between `min` and `max`, so fast speeds don't
+    /// outrun the frame rate and slow ones don't linger on long words
+    pub fn display_limits(mut self, min: Duration, max: Duration) -> Self {
+        self.min_display = min;
+        self.max_display = max.max(min);
+        self
+    }
+
+    /// Extra pauses driven by the text's layout
+    pub fn timing(mut self, timing: TimingConfig) -> Self {
+        self.timing = timing;
+        self
+    }
+
+    /// Time words with `strategy` instead of the one `timing.mode` names
+    pub fn timing_strategy(mut self, strategy: Box<dyn TimingStrategy>) -> Self {
+        self.strategy = Some(strategy);
+        self
+    }
+
+    /// On short seeks, give the new word the same share of its dwell already used
+    /// up as the old one had, instead of a fresh full dwell
+    pub fn seek_keeps_phase(mut self, keep: bool) -> Self {
+        self.seek_keeps_phase = keep;
+        self
+    }
+
+    /// Ease into a new speed set by `adjust_speed` or `set_wpm` over the next few
+    /// words, like the warmup, instead of switching at once
+    pub fn smooth_changes(mut self, smooth: bool) -> Self {
+        self.smooth_changes = smooth;
+        self
+    }
+
+    /// Step back this many words when resuming from a pause, to re-read them for context
+    pub fn rewind_on_resume(mut self, words: u32) -> Self {
+        self.rewind_on_resume = words as usize;
+        self
+    }
+
+    /// Place each word's focus character this far into it (0.0–1.0) rather than by
+    /// the classic length table (`None`, the default)
+    pub fn orp_position(mut self, position: Option<f32>) -> Self {
+        self.orp_position = position;
+        self
+    }
+
+    /// Flash up to `size` words at a time (1, the default, shows single words)
+    pub fn chunk_size(mut self, size: usize) -> Self {
+        self.chunk_size = size.max(1);
+        self
+    }
+
+    /// Show tokens longer than `chars` characters (URLs, hashes) in pieces
+    pub fn max_word_chars(mut self, chars: usize) -> Self {
+        self.max_word_chars = chars;
+        self
+    }
+
+    /// Keep the texts of the last `words` words shown, for `recent_words` (0 keeps none)
+    pub fn review_words(mut self, words: usize) -> Self {
+        self.review_words = words;
+        self
+    }
+
+    /// Continue a saved session: its words, position and speed replace the text and rates
+    pub fn restore(mut self, state: EngineState) -> Self {
+        self.restored = Some(state);
+        self
+    }
+
+    pub fn build(self) -> RSVPEngine {
+        let word_options = WordOptions {
+            tokenization: self.tokenization,
+            orp_position: self.orp_position,
+            chunk_size: self.chunk_size,
+            max_word_chars: self.max_word_chars,
+        };
+        let (words, current_index, start_wpm, target_wpm) = match self.restored {
+            Some(state) => {
+                let index = state.current_index.min(state.words.len());
+                (state.words, index, state.target_wpm, state.target_wpm)
+            }
+            None => {
+                let words = prepare_words(self.text, word_options, &self.timing);
+                (words, 0, self.start_wpm, self.target_wpm)
+            }
+        };
+
+        let mut engine = RSVPEngine {
+            words,
+            durations: Vec::new(),
+            current_index,
+            words_read: 0,
+            last_update: Instant::now(),
+            is_paused: false,
+            current_wpm: start_wpm,
+            target_wpm,
+            start_wpm,
+            warmup_words: self.warmup_words,
+            min_wpm: self.wpm_range.0,
+            max_wpm: self.wpm_range.1,
+            end_hold: self.end_hold,
+            stride: self.stride.max(1) as usize,
+            checkpoint_sentences: self.checkpoint_sentences,
+            sentences_read: 0,
+            at_checkpoint: false,
+            min_visible: self.min_visible,
+            shown_at: Instant::now(),
+            min_display: self.min_display,
+            max_display: self.max_display,
+            strategy: self.strategy.unwrap_or_else(|| strategy_for(self.timing.mode)),
+            timing: self.timing,
+            seek_keeps_phase: self.seek_keeps_phase,
+            smooth_changes: self.smooth_changes,
+            ramp: None,
+            rewind_on_resume: self.rewind_on_resume,
+            recent: Vec::new(),
+            review_words: self.review_words,
+            word_options,
+        };
+        engine.rebuild_durations();
+        engine
     }
 }
 
 pub struct RSVPEngine {
     words: Vec<Word>,
+    durations: Vec<Duration>, // Each word's display time at the target speed, rebuilt when timing changes
     current_index: usize,
+    words_read: usize, // Words advanced past this session (seeks don't count); drives the warmup
     last_update: Instant,
     is_paused: bool,
     current_wpm: u32,
     target_wpm: u32,
     start_wpm: u32,
     warmup_words: u32,
+    min_wpm: u32, // Bounds for speed changes
+    max_wpm: u32,
+    end_hold: Duration,
+    stride: usize, // Words advanced per step; > 1 when skimming
+    checkpoint_sentences: u32,
+    sentences_read: u32,
+    at_checkpoint: bool, // Paused at a study checkpoint, waiting for the reader
+    min_visible: Duration, // No word is replaced sooner than this after it appears
+    shown_at: Instant,     // When the current word appeared (by advance or seek)
+    min_display: Duration, // Bounds on any word's display time
+    max_display: Duration,
+    timing: TimingConfig,
+    strategy: Box<dyn TimingStrategy>,
+    seek_keeps_phase: bool,
+    smooth_changes: bool,
+    ramp: Option<(u32, usize)>, // Speed a smooth change started from, and `words_read` when it did
+    rewind_on_resume: usize, // Words replayed after a pause
+    recent: Vec<String>,     // Texts of the last words shown, oldest first, at most `review_words`
+    review_words: usize,
+    word_options: WordOptions, // Also used for text appended later
 }
 
 impl RSVPEngine {
+    /// Slowest and fastest speeds the engine will change to, unless `wpm_range` sets others
+    pub const MIN_WPM: u32 = 100;
+    pub const MAX_WPM: u32 = 1200;
+    /// Widest speed range `wpm_range` allows
+    pub const LOWEST_WPM: u32 = 25;
+    pub const HIGHEST_WPM: u32 = 3000;
+
+    /// Longest seek (in words) that keeps the dwell phase when `seek_keeps_phase` is on
+    const PHASE_SEEK_LIMIT: usize = 3;
+    /// Words a smooth speed change takes to reach the new speed
+    const RAMP_WORDS: usize = 10;
+    /// Refinements `fit_to_duration` makes to its speed estimate
+    const FIT_ROUNDS: usize = 3;
+
     pub fn new(text: &str, start_wpm: u32, target_wpm: u32, warmup_words: u32) -> Self {
-        let words: Vec<Word> = text
-            .split_whitespace()
-            .map(|w| Word::new(w.to_string()))
-            .collect();
+        Self::builder(text)
+            .start_wpm(start_wpm)
+            .target_wpm(target_wpm)
+            .warmup_words(warmup_words)
+            .build()
+    }
 
-        Self {
-            words,
-            current_index: 0,
-            last_update: Instant::now(),
-            is_paused: false,
-            current_wpm: start_wpm,
-            target_wpm,
-            start_wpm,
-            warmup_words,
-        }
+    pub fn builder(text: &str) -> RSVPEngineBuilder<'_> {
+        RSVPEngineBuilder::new(text)
+    }
+
+    /// Advance `stride` words per step to skim (1 reads every word)
+    pub fn set_stride(&mut self, stride: u32) {
+        self.stride = stride.max(1) as usize;
     }
 
     pub fn update(&mut self) -> Option<&Word> {
+        self.update_at(Instant::now())
+    }
+
+    /// Same as `update`, but against a caller-supplied clock. Advances at most one word
+    /// per call, so every word is returned (and drawn) at least once however late a
+    /// frame is, and returns the word now due.
+    pub fn update_at(&mut self, now: Instant) -> Option<&Word> {
         if self.is_paused || self.words.is_empty() || self.current_index >= self.words.len() {
             return None;
         }
+        if now.duration_since(self.shown_at) < self.min_visible {
+            return self.words.get(self.current_index);
+        }
 
-        // Calculate current WPM based on word count progress
-        if self.current_index < self.warmup_words as usize {
-            let progress = self.current_index as f32 / self.warmup_words as f32;
-            self.current_wpm = self.start_wpm +
-                ((self.target_wpm - self.start_wpm) as f32 * progress) as u32;
+        // Calculate current WPM from words read this session, so seeking neither
+        // skips the warmup nor restarts it. `warmup_words = 0` means no warmup at all.
+        // A smooth speed change ramps the same way, from where it started.
+        let ramp_done = self.ramp.is_some_and(|(_, since)| self.words_read >= since + Self::RAMP_WORDS);
+        if ramp_done {
+            self.ramp = None;
+        }
+        self.current_wpm = if self.warmup_words > 0 && self.words_read < self.warmup_words as usize {
+            let progress = self.words_read as f32 / self.warmup_words as f32;
+            interpolate_wpm(self.start_wpm, self.target_wpm, progress)
+        } else if let Some((from, since)) = self.ramp {
+            let progress = (self.words_read - since) as f32 / Self::RAMP_WORDS as f32;
+            interpolate_wpm(from, self.target_wpm, progress)
         } else {
-            self.current_wpm = self.target_wpm;
+            self.target_wpm
+        };
+
+        // Calculate display time for current word at current speed
+        let display_time = self.word_time(self.current_index, self.current_wpm);
+        let elapsed = now.duration_since(self.last_update);
+        if elapsed < display_time {
+            return self.words.get(self.current_index);
         }
 
-        let now = Instant::now();
+        // The word's time is used up; all of the overshoot counts toward the next words,
+        // so after a late frame the following frames catch up a word each and reading
+        // keeps its pace. With `min_visible` set every word stays up that long anyway,
+        // so at most one word's worth is owed.
+        let overshoot = elapsed - display_time;
+        let owed = if self.min_visible.is_zero() { overshoot } else { overshoot.min(display_time) };
+        self.last_update = now.checked_sub(owed).unwrap_or(now);
+        self.remember_shown();
+        let next_index = (self.current_index + self.stride).min(self.words.len());
+        let sentence_ends = self.words[self.current_index..next_index]
+            .iter()
+            .filter(|w| w.is_sentence_end(&self.timing.sentence_terminators))
+            .count() as u32;
+        self.words_read += self.words[self.current_index..next_index].iter().map(Word::word_count).sum::<usize>();
+        // A stride that overshoots the last word stops just past it
+        self.current_index = next_index;
+        self.shown_at = now;
+        self.count_sentences(sentence_ends);
 
-        // Calculate display time for current word at current speed
-        let base_duration = Duration::from_secs_f32(60.0 / self.current_wpm as f32);
-        let current_word = &self.words[self.current_index];
-        let length_factor = 1.0 + (current_word.text.len() as f32 - 5.0) * 0.03;
-        let punctuation_factor = if current_word.text.contains(&['.', '!', '?', ';'][..]) {
-            1.4
-        } else if current_word.text.contains(',') {
-            1.15
-        } else {
-            1.0
-        };
-        let display_time = base_duration.mul_f32(length_factor.max(0.8) * punctuation_factor);
+        self.words.get(self.current_index)
+    }
 
-        if now.duration_since(self.last_update) >= display_time {
-            self.last_update = now;
-            let word = &self.words[self.current_index];
-            self.current_index += 1;
-            Some(word)
-        } else {
-            Some(current_word)
+    /// Add text to the end of the read, for sources that keep growing (chat logs, live
+    /// transcripts). If every word had been shown, reading continues with the first new one.
+    pub fn append(&mut self, text: &str) {
+        let words = prepare_words(text, self.word_options, &self.timing);
+        if words.is_empty() {
+            return;
+        }
+
+        let old_len = self.words.len();
+        let durations: Vec<Duration> = words.iter().map(|word| self.display_time(word, self.target_wpm)).collect();
+        self.durations.extend(durations);
+        self.words.extend(words);
+        if self.current_index >= old_len {
+            // Reading had run through every word; carry on with the new text
+            self.last_update = Instant::now();
+            self.shown_at = self.last_update;
+        }
+    }
+
+    /// Track finished sentences and stop at study checkpoints
+    fn count_sentences(&mut self, sentence_ends: u32) {
+        if sentence_ends == 0 {
+            return;
+        }
+        let before = self.sentences_read;
+        self.sentences_read += sentence_ends;
+
+        // No checkpoint once the text is done; finishing says enough
+        let every = self.checkpoint_sentences;
+        let more_to_read = self.current_index < self.words.len();
+        if every > 0 && self.sentences_read / every > before / every && more_to_read {
+            self.at_checkpoint = true;
+            self.pause();
         }
     }
 
@@ -112,53 +781,326 @@ impl RSVPEngine {
     }
 
     pub fn resume(&mut self) {
+        if self.is_paused && self.rewind_on_resume > 0 {
+            self.current_index = self.current_index.saturating_sub(self.rewind_on_resume);
+            self.shown_at = Instant::now();
+        }
         self.is_paused = false;
-        self.last_update = Instant::now();
+        self.at_checkpoint = false;
+        self.reset_dwell();
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.is_paused
+    }
+
+    /// Paused at a study checkpoint until `resume`
+    pub fn is_at_checkpoint(&self) -> bool {
+        self.at_checkpoint
+    }
+
+    /// Restart the current word's display time from now
+    pub fn reset_dwell(&mut self) {
+        self.reset_dwell_at(Instant::now());
+    }
+
+    pub fn reset_dwell_at(&mut self, now: Instant) {
+        self.last_update = now;
     }
 
     pub fn reset(&mut self) {
+        self.recent.clear();
         self.current_index = 0;
+        self.words_read = 0;
+        self.sentences_read = 0;
         self.last_update = Instant::now();
+        self.shown_at = self.last_update;
         self.current_wpm = self.start_wpm;
+        self.ramp = None;
     }
 
     pub fn adjust_speed(&mut self, delta: i32) {
-        let new_wpm = (self.target_wpm as i32 + delta).max(100).min(1200) as u32;
+        self.set_wpm((self.target_wpm as i32 + delta).max(0) as u32);
+    }
+
+    /// Switch to `wpm` (clamped to the speed range): at once, skipping any warmup, or
+    /// with `smooth_changes` easing there from the current speed over the next few words
+    pub fn set_wpm(&mut self, wpm: u32) {
+        let new_wpm = wpm.clamp(self.min_wpm, self.max_wpm);
+        if self.smooth_changes && new_wpm != self.current_wpm {
+            self.ramp = Some((self.current_wpm, self.words_read));
+        } else {
+            self.ramp = None;
+            self.current_wpm = new_wpm;
+        }
         self.target_wpm = new_wpm;
-        self.current_wpm = new_wpm;
+        self.rebuild_durations();
+    }
+
+    /// Set the speed so the rest of the text, from the current word on, takes about
+    /// `duration` at the target pace, pauses included. Clamped to the speed range, with
+    /// a warning when that can't fit.
+    pub fn fit_to_duration(&mut self, duration: Duration) {
+        let remaining = &self.words[self.current_index.min(self.words.len())..];
+        let seconds = duration.as_secs_f32();
+        let (min, max) = (self.min_wpm as f32, self.max_wpm as f32);
+        let mut needed = if seconds > 0.0 { self.target_wpm as f32 } else { f32::INFINITY };
+        // Display times shrink in proportion to the speed, except where the display limits
+        // or fixed symbol dwells step in, so scale by the real total and refine
+        for _ in 0..Self::FIT_ROUNDS {
+            if !needed.is_finite() {
+                break;
+            }
+            let trial = needed.clamp(min, max).round() as u32;
+            let total: Duration = remaining.iter().map(|word| self.display_time(word, trial)).sum();
+            if total.is_zero() {
+                break;
+            }
+            needed = trial as f32 * total.as_secs_f32() / seconds;
+        }
+        let wpm = needed.clamp(min, max).round() as u32;
+        if needed.round() as u32 != wpm {
+            log::warn!(
+                "Can't read {} words in {:?}: would need {:.0} wpm, using {}",
+                remaining.iter().map(Word::word_count).sum::<usize>(),
+                duration,
+                needed,
+                wpm
+            );
+        }
+        self.target_wpm = wpm;
+        self.current_wpm = wpm;
+        self.ramp = None;
+        self.rebuild_durations();
     }
 
     pub fn seek(&mut self, delta: i32) {
+        self.seek_at(delta, Instant::now());
+    }
+
+    pub fn seek_at(&mut self, delta: i32, now: Instant) {
         let new_index = (self.current_index as i32 + delta).max(0) as usize;
-        self.current_index = new_index.min(self.words.len().saturating_sub(1));
-        self.last_update = Instant::now();
+        self.seek_to_at(new_index, now);
+    }
+
+    /// Jump to the start of the next sentence (`direction` > 0), or back to the start of
+    /// the current one, or of the one before when already at a start (`direction` < 0)
+    pub fn seek_sentence(&mut self, direction: i32) {
+        let target = if direction > 0 {
+            (self.current_index + 1..self.words.len()).find(|&i| self.starts_sentence(i))
+        } else if direction < 0 {
+            (0..self.current_index.min(self.words.len())).rev().find(|&i| self.starts_sentence(i))
+        } else {
+            None
+        };
+        if let Some(index) = target {
+            self.seek_to(index);
+        }
+    }
+
+    /// Indices of the words of the sentence holding the current word (the last word
+    /// once finished), or None for an empty text
+    pub fn sentence_bounds(&self) -> Option<std::ops::Range<usize>> {
+        let index = self.current_index.min(self.words.len().checked_sub(1)?);
+        let start = (0..=index).rev().find(|&i| self.starts_sentence(i)).unwrap_or(0);
+        let end = (index..self.words.len())
+            .find(|&i| self.words[i].is_sentence_end(&self.timing.sentence_terminators))
+            .map_or(self.words.len(), |i| i + 1);
+        Some(start..end)
+    }
+
+    /// Whether word `index` is the first of a sentence
+    fn starts_sentence(&self, index: usize) -> bool {
+        index == 0 || self.words[index - 1].is_sentence_end(&self.timing.sentence_terminators)
     }
 
     pub fn seek_to(&mut self, index: usize) {
-        self.current_index = index.min(self.words.len().saturating_sub(1));
-        self.last_update = Instant::now();
+        self.seek_to_at(index, Instant::now());
+    }
+
+    pub fn seek_to_at(&mut self, index: usize, now: Instant) {
+        let index = index.min(self.words.len().saturating_sub(1));
+        let short = index.abs_diff(self.current_index) <= Self::PHASE_SEEK_LIMIT;
+        let phase = if self.seek_keeps_phase && short && !self.is_paused {
+            self.dwell_phase(now)
+        } else {
+            0.0
+        };
+
+        if index != self.current_index {
+            self.remember_shown();
+        }
+        self.current_index = index;
+        let used = self.current_display_time().unwrap_or_default().mul_f32(phase);
+        self.last_update = now.checked_sub(used).unwrap_or(now);
+        self.shown_at = now;
+    }
+
+    /// How long the current word stays up at the current speed
+    pub fn current_display_time(&self) -> Option<Duration> {
+        (self.current_index < self.words.len()).then(|| self.word_time(self.current_index, self.current_wpm))
+    }
+
+    /// How long until `update_at` would show something new: the rest of the current
+    /// word's display time (and any `min_visible` lockout), or of the end hold once past
+    /// the last word. None while paused, when nothing changes until `resume`.
+    pub fn time_until_change_at(&self, now: Instant) -> Option<Duration> {
+        if self.is_paused {
+            return None;
+        }
+        let elapsed = now.duration_since(self.last_update);
+        let wait = match self.current_display_time() {
+            Some(display_time) => {
+                let locked_for = self.min_visible.saturating_sub(now.duration_since(self.shown_at));
+                display_time.saturating_sub(elapsed).max(locked_for)
+            }
+            None => self.end_hold.saturating_sub(elapsed),
+        };
+        Some(wait)
+    }
+
+    /// Display time of word `index` at `wpm`: the cached one at the target speed, so
+    /// only the warmup (reading below target) works it out afresh
+    fn word_time(&self, index: usize, wpm: u32) -> Duration {
+        if wpm == self.target_wpm {
+            self.durations[index]
+        } else {
+            self.display_time(&self.words[index], wpm)
+        }
+    }
+
+    /// Work out every word's display time at the target speed, after the speed or
+    /// timing mode changes
+    fn rebuild_durations(&mut self) {
+        self.durations = self.words.iter().map(|word| self.display_time(word, self.target_wpm)).collect();
+    }
+
+    /// The strategy's time for `word`, held within the configured display limits
+    fn display_time(&self, word: &Word, wpm: u32) -> Duration {
+        self.strategy.word_duration(word, wpm, &self.timing).clamp(self.min_display, self.max_display)
+    }
+
+    /// Share of the current word's dwell already elapsed, in [0, 1)
+    fn dwell_phase(&self, now: Instant) -> f32 {
+        match self.current_display_time() {
+            Some(display_time) if !display_time.is_zero() => {
+                let elapsed = now.duration_since(self.last_update);
+                (elapsed.as_secs_f32() / display_time.as_secs_f32()).min(0.99)
+            }
+            _ => 0.0,
+        }
+    }
+
+    /// Add the current word to `recent`, as it leaves the screen
+    fn remember_shown(&mut self) {
+        if self.review_words == 0 {
+            return;
+        }
+        if let Some(word) = self.words.get(self.current_index) {
+            self.recent.push(word.text.clone());
+            if self.recent.len() > self.review_words {
+                self.recent.remove(0);
+            }
+        }
+    }
+
+    /// The last words shown before the current one, oldest first. Seeks count too:
+    /// these are the words the reader saw, not the ones before it in the text.
+    pub fn recent_words(&self) -> &[String] {
+        &self.recent
     }
 
     pub fn get_current_index(&self) -> usize {
         self.current_index
     }
 
+    /// Words read through since the start (or restart) of this session; seeks don't count
+    pub fn words_read(&self) -> usize {
+        self.words_read
+    }
+
+    /// Snapshot for crash recovery
+    pub fn state(&self) -> EngineState {
+        EngineState {
+            words: self.words.clone(),
+            current_index: self.current_index,
+            target_wpm: self.target_wpm,
+        }
+    }
+
+    /// 1-based number of the word on screen (the last word once finished)
+    pub fn position(&self) -> usize {
+        (self.current_index + 1).min(self.words.len())
+    }
+
+    pub fn total_words(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn words(&self) -> &[Word] {
+        &self.words
+    }
+
     pub fn get_current_word(&self) -> Option<&Word> {
         self.words.get(self.current_index)
     }
 
     pub fn is_finished(&self) -> bool {
+        self.is_finished_at(Instant::now())
+    }
+
+    /// Finished once past the last word and the end hold has elapsed
+    pub fn is_finished_at(&self, now: Instant) -> bool {
+        if self.words.is_empty() {
+            return true;
+        }
         self.current_index >= self.words.len()
+            && now.duration_since(self.last_update) >= self.end_hold
     }
 
     pub fn get_progress(&self) -> f32 {
         if self.words.is_empty() {
             0.0
         } else {
-            self.current_index as f32 / self.words.len() as f32
+            self.current_index.min(self.words.len()) as f32 / self.words.len() as f32
+        }
+    }
+
+    /// Progress by expected reading time rather than word count, so long or
+    /// punctuated words weigh more. Uses each word's dwell at the target speed.
+    pub fn time_progress(&self) -> f32 {
+        let split = self.current_index.min(self.durations.len());
+        let elapsed: Duration = self.durations[..split].iter().sum();
+        let total: Duration = elapsed + self.durations[split..].iter().sum::<Duration>();
+
+        if total.is_zero() {
+            0.0
+        } else {
+            elapsed.as_secs_f32() / total.as_secs_f32()
         }
     }
 
+    /// Switch to the strategy behind `mode` (replacing one passed to the builder); the
+    /// current word's timing follows at once
+    pub fn set_timing_mode(&mut self, mode: TimingMode) {
+        self.timing.mode = mode;
+        self.strategy = strategy_for(mode);
+        self.rebuild_durations();
+    }
+
+    /// Expected time to read the rest of the text at the target speed
+    pub fn estimated_remaining(&self) -> Duration {
+        self.durations.iter().skip(self.current_index).sum()
+    }
+
+    /// How much longer than a plain word the next word to be shown will stay up
+    /// (1.0 = baseline dwell at the target speed), or None at the end
+    pub fn upcoming_pause_factor(&self) -> Option<f32> {
+        let next = self.durations.get(self.current_index + self.stride)?;
+        let baseline = 60.0 / self.target_wpm as f32;
+        Some(next.as_secs_f32() / baseline)
+    }
+
     pub fn get_current_wpm(&self) -> u32 {
         self.current_wpm
     }
@@ -166,4 +1108,927 @@ impl RSVPEngine {
     pub fn get_target_wpm(&self) -> u32 {
         self.target_wpm
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Plain pacing at 300 wpm: 200 ms for an ordinary five-letter word, no warmup
+    fn engine(text: &str) -> RSVPEngine {
+        RSVPEngine::builder(text).start_wpm(300).target_wpm(300).warmup_words(0).build()
+    }
+
+    /// Whole milliseconds, rounding away float noise in computed display times
+    fn ms(duration: Duration) -> u64 {
+        (duration.as_secs_f64() * 1000.0).round() as u64
+    }
+
+    /// Step the engine through its words on a simulated clock, a frame every `frame`
+    fn run_until_finished(engine: &mut RSVPEngine, start: Instant, frame: Duration) -> Instant {
+        let mut now = start;
+        engine.reset_dwell_at(start);
+        while !engine.is_finished_at(now) {
+            now += frame;
+            engine.update_at(now);
+        }
+        now
+    }
+
+    /// Step `engine` in 16 ms frames from `start` until `until` has passed
+    fn run_frames(engine: &mut RSVPEngine, start: Instant, until: Duration) {
+        let frame = Duration::from_millis(16);
+        engine.reset_dwell_at(start);
+        let mut now = start;
+        while now + frame <= start + until {
+            now += frame;
+            engine.update_at(now);
+        }
+    }
+
+    #[test]
+    fn orp_follows_length_table() {
+        for (word, orp) in [("a", 0), ("the", 0), ("word", 1), ("hello", 1), ("reading", 2), ("comprehension", 3), ("internationalization", 4)] {
+            assert_eq!(Word::new(word.to_string()).orp_index, orp, "{}", word);
+        }
+    }
+
+    #[test]
+    fn orp_parts_split_around_focus_letter() {
+        let word = Word::new("reading".to_string());
+        assert_eq!(word.get_parts(), Some(("re".to_string(), 'a', "ding".to_string())));
+    }
+
+    #[test]
+    fn display_time_follows_speed() {
+        let mut engine = engine("hello world");
+        assert_eq!(engine.current_display_time().map(ms), Some(200));
+        engine.adjust_speed(300);
+        assert_eq!(engine.current_display_time().map(ms), Some(100));
+    }
+
+    #[test]
+    fn sentence_end_stays_longer() {
+        let timing = TimingConfig::default();
+        let plain = word_display_time(&Word::new("hello".to_string()), 300, &timing);
+        let end = word_display_time(&Word::new("hello.".to_string()), 300, &timing);
+        assert!(end > plain);
+    }
+
+    #[test]
+    fn paused_engine_does_not_advance() {
+        let mut engine = engine("one two three four");
+        let start = Instant::now();
+        engine.reset_dwell_at(start);
+        engine.pause();
+        assert!(engine.update_at(start + Duration::from_secs(10)).is_none());
+        assert_eq!(engine.get_current_index(), 0);
+
+        engine.resume();
+        assert!(!engine.is_paused());
+        engine.update_at(Instant::now() + Duration::from_millis(250));
+        assert_eq!(engine.get_current_index(), 1);
+    }
+
+    #[test]
+    fn progress_counts_words_shown() {
+        let mut engine = engine("one two three four");
+        assert_eq!(engine.get_progress(), 0.0);
+        let start = Instant::now();
+        run_frames(&mut engine, start, Duration::from_millis(450));
+        assert_eq!(engine.get_current_index(), 2);
+        assert_eq!(engine.get_progress(), 0.5);
+
+        run_until_finished(&mut engine, start, Duration::from_millis(16));
+        assert_eq!(engine.get_progress(), 1.0);
+    }
+
+    #[test]
+    fn stride_visits_every_nth_word() {
+        let mut engine = RSVPEngine::builder("a b c d e f g h i j").target_wpm(300).warmup_words(0).stride(3).build();
+        let start = Instant::now();
+        engine.reset_dwell_at(start);
+        let mut visited = vec![engine.get_current_index()];
+        let mut now = start;
+        while engine.get_current_index() < engine.total_words() {
+            now += Duration::from_millis(16);
+            engine.update_at(now);
+            if visited.last() != Some(&engine.get_current_index()) {
+                visited.push(engine.get_current_index());
+            }
+        }
+        assert_eq!(visited, [0, 3, 6, 9, 10]);
+    }
+
+    #[test]
+    fn stride_overshoot_keeps_progress_at_one() {
+        let mut engine = RSVPEngine::builder("a b c d e f g h i j").target_wpm(300).warmup_words(0).stride(3).build();
+        run_until_finished(&mut engine, Instant::now(), Duration::from_millis(16));
+        assert_eq!(engine.get_progress(), 1.0);
+        assert_eq!(engine.get_current_index(), engine.total_words());
+    }
+
+    /// Simulated time `engine` takes to read from its current word to the end
+    fn reading_time(engine: &mut RSVPEngine) -> Duration {
+        let start = Instant::now();
+        run_until_finished(engine, start, Duration::from_millis(5)) - start
+    }
+
+    #[test]
+    fn time_box_includes_pauses() {
+        let text = "Reading quickly, and reading well. ".repeat(200);
+        let mut engine = RSVPEngine::builder(&text).target_wpm(400).warmup_words(0).build();
+        engine.fit_to_duration(Duration::from_secs(300));
+        let secs = reading_time(&mut engine).as_secs_f32();
+        assert!((secs - 300.0).abs() <= 6.0, "took {:.1}s", secs);
+    }
+
+    #[test]
+    fn time_box_sets_speed_from_word_count() {
+        let text = "word ".repeat(600);
+        let mut engine = RSVPEngine::builder(&text).timing_strategy(Box::new(ConstantTiming)).warmup_words(0).build();
+        engine.fit_to_duration(Duration::from_secs(120));
+        assert_eq!(engine.get_target_wpm(), 300);
+    }
+
+    #[test]
+    fn time_box_fits_the_words_left_after_a_resume() {
+        let text = "word ".repeat(600);
+        let mut engine = RSVPEngine::builder(&text).timing_strategy(Box::new(ConstantTiming)).warmup_words(0).build();
+        engine.seek_to(300);
+        engine.fit_to_duration(Duration::from_secs(120));
+        assert_eq!(engine.get_target_wpm(), 150);
+        let secs = reading_time(&mut engine).as_secs_f32();
+        assert!((secs - 120.0).abs() <= 2.0, "took {:.1}s", secs);
+    }
+
+    #[test]
+    fn impossible_time_box_is_clamped() {
+        let mut engine = engine(&"word ".repeat(100));
+        engine.fit_to_duration(Duration::from_secs(1));
+        assert_eq!(engine.get_target_wpm(), RSVPEngine::MAX_WPM);
+    }
+
+    #[test]
+    fn time_box_counts_chunked_words() {
+        let text = "Reading quickly, and reading well. ".repeat(200);
+        let mut engine = RSVPEngine::builder(&text).target_wpm(400).warmup_words(0).chunk_size(3).build();
+        assert!(engine.total_words() < 1000);
+        engine.fit_to_duration(Duration::from_secs(300));
+        let secs = reading_time(&mut engine).as_secs_f32();
+        assert!((secs - 300.0).abs() <= 6.0, "took {:.1}s", secs);
+    }
+
+    #[test]
+    fn estimated_remaining_shrinks_as_words_advance() {
+        let mut engine = engine("The quick brown fox, it jumps over the lazy dog. Then it rests.");
+        let mut previous = engine.estimated_remaining();
+        for index in 1..engine.total_words() {
+            engine.seek_to(index);
+            let remaining = engine.estimated_remaining();
+            assert!(remaining < previous, "word {}", index);
+            previous = remaining;
+        }
+    }
+
+    #[test]
+    fn estimated_remaining_follows_speed_changes() {
+        let mut engine = engine(&"word ".repeat(50));
+        let before = engine.estimated_remaining();
+        engine.adjust_speed(300);
+        assert_eq!(ms(engine.estimated_remaining()), ms(before) / 2);
+    }
+
+    #[test]
+    fn syllable_estimates() {
+        for (word, syllables) in [("strength", 1), ("banana", 3), ("queueing", 2), ("cake", 1), ("table", 2), ("rhythm", 1), ("nth", 1), ("Being,", 2)] {
+            assert_eq!(syllable_count(word), syllables, "{}", word);
+        }
+    }
+
+    #[test]
+    fn syllable_timing_adds_time_per_syllable() {
+        let timing = TimingConfig::default();
+        let one = SyllableTiming.word_duration(&Word::new("strength".to_string()), 300, &timing);
+        let three = SyllableTiming.word_duration(&Word::new("banana".to_string()), 300, &timing);
+        assert_eq!(ms(one), 200);
+        assert_eq!(ms(three), 232); // Two syllables past the first, 8% each
+        assert_eq!(SyllableTiming.duration("banana", 300), three);
+    }
+
+    #[test]
+    fn every_timing_mode_has_its_strategy() {
+        let timing = TimingConfig::default();
+        let word = Word::new("extraordinary,".to_string());
+        for mode in TimingMode::ALL {
+            let via_mode = word_display_time(&word, 300, &TimingConfig { mode, ..timing.clone() });
+            assert_eq!(via_mode, strategy_for(mode).word_duration(&word, 300, &timing), "{:?}", mode);
+        }
+        assert_eq!(ms(strategy_for(TimingMode::Constant).word_duration(&word, 300, &timing)), 200);
+        assert_eq!(ms(ConstantTiming.duration("extraordinary,", 300)), 200);
+    }
+
+    #[test]
+    fn recent_words_keep_the_last_few_shown() {
+        let mut engine = RSVPEngine::builder("one two three four five six").target_wpm(300).warmup_words(0).review_words(3).build();
+        let start = Instant::now();
+        engine.reset_dwell_at(start);
+        let mut now = start;
+        while engine.get_current_index() < 5 {
+            now += Duration::from_millis(16);
+            engine.update_at(now);
+        }
+        assert_eq!(engine.recent_words(), ["three", "four", "five"]);
+        assert_eq!(engine.get_current_index(), 5);
+    }
+
+    #[test]
+    fn last_word_holds_before_finishing() {
+        let mut engine = RSVPEngine::builder("hello world").target_wpm(300).warmup_words(0).end_hold(Duration::from_millis(500)).build();
+        let start = Instant::now();
+        run_frames(&mut engine, start, Duration::from_millis(450));
+        assert_eq!(engine.get_current_index(), engine.total_words());
+        assert!(!engine.is_finished_at(start + Duration::from_millis(450)));
+        assert!(!engine.is_finished_at(start + Duration::from_millis(899)));
+        assert!(engine.is_finished_at(start + Duration::from_millis(901)));
+    }
+
+    #[test]
+    fn no_end_hold_finishes_with_the_last_word() {
+        let mut engine = engine("hello world");
+        let start = Instant::now();
+        run_frames(&mut engine, start, Duration::from_millis(450));
+        assert!(engine.is_finished_at(start + Duration::from_millis(450)));
+    }
+
+    #[test]
+    fn time_progress_weighs_slow_words_more() {
+        // Short words first, then long punctuated ones that take longer each
+        let mut engine = engine("a b c d e extraordinarily, unquestionably. incomprehensible! revolutionaries.");
+        assert_eq!(engine.time_progress(), 0.0);
+        engine.seek_to(5);
+        assert_eq!(engine.get_progress(), 5.0 / 9.0);
+        assert!(engine.time_progress() < engine.get_progress() * 0.75, "{}", engine.time_progress());
+
+        engine.seek_to(8);
+        let start = Instant::now();
+        engine.reset_dwell_at(start);
+        engine.update_at(start + Duration::from_secs(5));
+        assert_eq!(engine.time_progress(), 1.0);
+        assert_eq!(engine.get_progress(), 1.0);
+    }
+
+    #[test]
+    fn frame_limited_engine_advances_one_word_per_step() {
+        let mut engine = RSVPEngine::builder(&"word ".repeat(20)).target_wpm(1200).warmup_words(0).min_visible(Duration::from_millis(16)).build();
+        let start = Instant::now();
+        engine.reset_dwell_at(start);
+        engine.update_at(start + Duration::from_secs(1));
+        assert_eq!(engine.get_current_index(), 1);
+    }
+
+    #[test]
+    fn dwell_reset_after_a_hidden_gap_resumes_without_a_burst() {
+        let mut engine = engine(&"word ".repeat(100));
+        let start = Instant::now();
+        engine.reset_dwell_at(start);
+        engine.update_at(start + Duration::from_millis(250));
+        assert_eq!(engine.get_current_index(), 1);
+
+        // Hidden for a minute; regaining focus restarts the word's display time
+        let shown = start + Duration::from_secs(60);
+        engine.reset_dwell_at(shown);
+        engine.update_at(shown + Duration::from_millis(16));
+        assert_eq!(engine.get_current_index(), 1);
+        engine.update_at(shown + Duration::from_millis(210));
+        assert_eq!(engine.get_current_index(), 2);
+    }
+
+    #[test]
+    fn builder_defaults_match_new() {
+        let text = "Builders keep construction readable. Don't they?";
+        let old = RSVPEngine::new(text, 250, 450, 5);
+        let built = RSVPEngine::builder(text).start_wpm(250).target_wpm(450).warmup_words(5).build();
+        let texts = |engine: &RSVPEngine| engine.words().iter().map(|w| w.text.clone()).collect::<Vec<_>>();
+        assert_eq!(texts(&old), texts(&built));
+        assert_eq!(old.get_current_wpm(), built.get_current_wpm());
+        assert_eq!(old.get_target_wpm(), built.get_target_wpm());
+        assert_eq!(old.estimated_remaining(), built.estimated_remaining());
+    }
+
+    #[test]
+    fn builder_overrides_take_effect() {
+        let engine = RSVPEngine::builder("one two three four five six")
+            .start_wpm(200)
+            .target_wpm(600)
+            .wpm_range(150, 900)
+            .chunk_size(2)
+            .build();
+        assert_eq!(engine.get_current_wpm(), 200);
+        assert_eq!(engine.get_target_wpm(), 600);
+        assert_eq!(engine.total_words(), 3);
+        assert_eq!(engine.words()[0].text, "one two");
+
+        let mut engine = engine;
+        engine.adjust_speed(1000);
+        assert_eq!(engine.get_target_wpm(), 900);
+    }
+
+    #[test]
+    fn checkpoint_pauses_after_every_third_sentence() {
+        let text = "One. Two. Three. Four. Five. Six. Seven.";
+        let mut engine = RSVPEngine::builder(text).target_wpm(300).warmup_words(0).checkpoint_sentences(3).build();
+        run_frames(&mut engine, Instant::now(), Duration::from_secs(60));
+        assert!(engine.is_at_checkpoint());
+        assert!(engine.is_paused());
+        assert_eq!(engine.get_current_index(), 3);
+
+        engine.resume();
+        assert!(!engine.is_at_checkpoint());
+        run_frames(&mut engine, Instant::now(), Duration::from_secs(60));
+        assert!(engine.is_at_checkpoint());
+        assert_eq!(engine.get_current_index(), 6);
+    }
+
+    #[test]
+    fn no_checkpoint_at_the_end_of_the_text() {
+        let mut engine = RSVPEngine::builder("One. Two. Three.").target_wpm(300).warmup_words(0).checkpoint_sentences(3).build();
+        let end = run_until_finished(&mut engine, Instant::now(), Duration::from_millis(16));
+        assert!(!engine.is_at_checkpoint());
+        assert!(engine.is_finished_at(end));
+    }
+
+    #[test]
+    fn min_visible_locks_out_a_second_advance() {
+        let mut engine = RSVPEngine::builder(&"word ".repeat(20)).target_wpm(1200).warmup_words(0).min_visible(Duration::from_millis(100)).build();
+        let start = Instant::now();
+        engine.reset_dwell_at(start);
+        let first = start + Duration::from_secs(10);
+        engine.update_at(first);
+        assert_eq!(engine.get_current_index(), 1);
+        engine.update_at(first + Duration::from_millis(99));
+        assert_eq!(engine.get_current_index(), 1);
+        assert_eq!(engine.time_until_change_at(first + Duration::from_millis(60)), Some(Duration::from_millis(40)));
+        engine.update_at(first + Duration::from_millis(100));
+        assert_eq!(engine.get_current_index(), 2);
+    }
+
+    #[test]
+    fn min_visible_applies_after_a_seek() {
+        let mut engine = RSVPEngine::builder(&"word ".repeat(20)).target_wpm(1200).warmup_words(0).min_visible(Duration::from_millis(100)).build();
+        let start = Instant::now();
+        engine.seek_to_at(5, start);
+        engine.update_at(start + Duration::from_millis(90));
+        assert_eq!(engine.get_current_index(), 5);
+        engine.update_at(start + Duration::from_millis(100));
+        assert_eq!(engine.get_current_index(), 6);
+    }
+
+    #[test]
+    fn stanza_line_ends_get_the_line_break_pause() {
+        let timing = TimingConfig { respect_line_breaks: true, ..TimingConfig::default() };
+        let engine = RSVPEngine::builder("roses bloom\nlilac sighs\nnight falls\n").target_wpm(300).warmup_words(0).timing(timing.clone()).build();
+        let line_ends: Vec<&Word> = engine.words().iter().filter(|w| w.break_after != BreakKind::None).collect();
+        assert_eq!(line_ends.iter().map(|w| w.text.as_str()).collect::<Vec<_>>(), ["bloom", "sighs", "falls"]);
+        for word in line_ends {
+            assert_eq!(word.break_after, BreakKind::Line, "{}", word.text);
+            assert_eq!(ms(word_display_time(word, 300, &timing)), 260, "{}", word.text); // 1.3x, not 2x
+        }
+        assert_eq!(ms(word_display_time(&engine.words()[0], 300, &timing)), 200);
+    }
+
+    #[test]
+    fn line_breaks_only_pause_when_respected() {
+        let mut word = Word::new("falls".to_string());
+        word.break_after = BreakKind::Line;
+        assert_eq!(ms(word_display_time(&word, 300, &TimingConfig::default())), 200);
+        word.break_after = BreakKind::Paragraph;
+        assert_eq!(ms(word_display_time(&word, 300, &TimingConfig::default())), 400);
+    }
+
+    #[test]
+    fn custom_terminators_change_dwell_and_sentence_seeks() {
+        let text = "Note this: colons split. Here";
+        let stock = TimingConfig::default();
+        let with_colon = TimingConfig { sentence_terminators: format!("{}:", stock.sentence_terminators), ..stock.clone() };
+        let word = Word::new("this:".to_string());
+        assert!(word_display_time(&word, 300, &with_colon) > word_display_time(&word, 300, &stock));
+
+        let mut plain = RSVPEngine::builder(text).timing(stock).build();
+        plain.seek_sentence(1);
+        assert_eq!(plain.get_current_index(), 4);
+
+        let mut colon = RSVPEngine::builder(text).timing(with_colon).build();
+        colon.seek_sentence(1);
+        assert_eq!(colon.get_current_index(), 2);
+        colon.seek_sentence(1);
+        assert_eq!(colon.get_current_index(), 4);
+        assert_eq!(colon.sentence_bounds(), Some(4..5));
+    }
+
+    #[test]
+    fn upcoming_pause_factor_looks_at_the_next_word() {
+        let mut engine = engine("hello there done. again");
+        assert_eq!(engine.upcoming_pause_factor().map(|f| (f * 100.0).round()), Some(100.0));
+        engine.seek_to(1);
+        assert_eq!(engine.upcoming_pause_factor().map(|f| (f * 100.0).round()), Some(140.0));
+        engine.seek_to(3);
+        assert_eq!(engine.upcoming_pause_factor(), None);
+    }
+
+    /// When the word after a one-word seek at 100 ms into a 200 ms dwell comes up
+    fn next_advance_after_nudge(keep_phase: bool) -> Duration {
+        let mut engine = RSVPEngine::builder(&"hello ".repeat(10)).target_wpm(300).warmup_words(0).seek_keeps_phase(keep_phase).build();
+        let start = Instant::now();
+        engine.reset_dwell_at(start);
+        let seek_at = start + Duration::from_millis(100);
+        engine.seek_at(1, seek_at);
+        engine.time_until_change_at(seek_at).unwrap()
+    }
+
+    #[test]
+    fn small_seek_keeps_or_restarts_the_dwell_phase() {
+        assert_eq!(ms(next_advance_after_nudge(false)), 200);
+        assert_eq!(ms(next_advance_after_nudge(true)), 100);
+    }
+
+    #[test]
+    fn long_seek_always_restarts_the_dwell() {
+        let mut engine = RSVPEngine::builder(&"hello ".repeat(10)).target_wpm(300).warmup_words(0).seek_keeps_phase(true).build();
+        let start = Instant::now();
+        engine.reset_dwell_at(start);
+        let seek_at = start + Duration::from_millis(100);
+        engine.seek_to_at(8, seek_at);
+        assert_eq!(engine.time_until_change_at(seek_at).map(ms), Some(200));
+    }
+
+    #[test]
+    fn each_word_in_mixed_text_has_its_own_direction() {
+        let engine = engine("Install مرحبا package");
+        let directions: Vec<TextDirection> = engine.words().iter().map(|w| w.direction).collect();
+        assert_eq!(directions, [TextDirection::Ltr, TextDirection::Rtl, TextDirection::Ltr]);
+    }
+
+    #[test]
+    fn orp_char_matches_get_parts() {
+        for text in ["reading", "naïveté", "日本語", "...", "!?", "x"] {
+            let word = Word::new(text.to_string());
+            let focus = word.get_parts().map(|(_, focus, _)| focus);
+            assert_eq!(word.orp_char(), focus, "{}", text);
+        }
+    }
+
+    #[test]
+    fn orp_char_counts_characters_not_bytes() {
+        assert_eq!(Word::new("reading".to_string()).orp_char(), Some('a'));
+        assert_eq!(Word::new("éclair".to_string()).orp_char(), Some('l'));
+        assert_eq!(Word::new("日本語です".to_string()).orp_char(), Some('本'));
+        assert_eq!(Word::new("...".to_string()).orp_char(), Some('.'));
+    }
+
+    #[test]
+    fn repeated_terminators_pause_longer() {
+        let timing = TimingConfig::default();
+        let time = |text: &str| word_display_time(&Word::new(text.to_string()), 300, &timing);
+        assert!(time("What?!") > time("What?"));
+        assert!(time("Wait...") > time("Wait."));
+        // Runs past three add nothing more (syllable timing, so the extra dot adds no length)
+        let syllable_time = |text: &str| SyllableTiming.word_duration(&Word::new(text.to_string()), 300, &timing);
+        assert_eq!(syllable_time("Wait...."), syllable_time("Wait..."));
+        assert!(syllable_time("Wait...") > syllable_time("Wait.."));
+    }
+
+    /// Step `engine` on a simulated clock until it has advanced `words` more words
+    fn read_words(engine: &mut RSVPEngine, words: usize) {
+        let target = engine.words_read() + words;
+        let start = Instant::now();
+        engine.reset_dwell_at(start);
+        let mut now = start;
+        while engine.words_read() < target {
+            now += Duration::from_millis(5);
+            engine.update_at(now);
+        }
+    }
+
+    #[test]
+    fn seeking_forward_keeps_the_warmup() {
+        let mut engine = RSVPEngine::new(&"word ".repeat(100), 200, 400, 10);
+        engine.seek_to(50);
+        read_words(&mut engine, 1);
+        assert!(engine.get_current_wpm() < 250, "{}", engine.get_current_wpm());
+        read_words(&mut engine, 10);
+        assert_eq!(engine.get_current_wpm(), 400);
+    }
+
+    #[test]
+    fn seeking_back_does_not_restart_the_warmup() {
+        let mut engine = RSVPEngine::new(&"word ".repeat(100), 200, 400, 10);
+        read_words(&mut engine, 20);
+        assert_eq!(engine.get_current_wpm(), 400);
+        engine.seek_to(3);
+        read_words(&mut engine, 1);
+        assert_eq!(engine.get_current_wpm(), 400);
+        assert_eq!(engine.words_read(), 21);
+    }
+
+    #[test]
+    fn worst_case_pause_is_capped() {
+        let timing = TimingConfig::default();
+        let mut word = Word::new("Incomprehensibly!!!".to_string());
+        word.break_after = BreakKind::Paragraph;
+        word.heading = true;
+        word.emphasis = true;
+        assert_eq!(ms(word_display_time(&word, 300, &timing)), 600); // 3x the 200 ms baseline
+
+        let tighter = TimingConfig { max_pause_factor: 2.0, ..timing };
+        assert_eq!(ms(word_display_time(&word, 300, &tighter)), 400);
+    }
+
+    #[test]
+    fn switching_timing_modes_retimes_the_current_word() {
+        let mut engine = engine("extraordinary, words");
+        let char_length = engine.current_display_time().unwrap();
+        engine.set_timing_mode(TimingMode::Constant);
+        assert_eq!(engine.current_display_time().map(ms), Some(200));
+        engine.set_timing_mode(TimingMode::Cpm);
+        let cpm = engine.current_display_time().unwrap();
+        assert!(cpm > char_length, "{:?} vs {:?}", cpm, char_length);
+        engine.set_timing_mode(TimingMode::CharLength);
+        assert_eq!(engine.current_display_time(), Some(char_length));
+    }
+
+    #[test]
+    fn focus_letter_is_chosen_by_character_for_unicode_words() {
+        let parts = |text: &str| Word::new(text.to_string()).get_parts().unwrap();
+        assert_eq!(parts("naïve"), ("n".to_string(), 'a', "ïve".to_string()));
+        assert_eq!(parts("café"), ("c".to_string(), 'a', "fé".to_string()));
+        assert_eq!(parts("👍great"), ("👍g".to_string(), 'r', "eat".to_string()));
+        assert_eq!(parts("👍!"), (String::new(), '👍', "!".to_string()));
+        assert_eq!(parts("reading"), ("re".to_string(), 'a', "ding".to_string()));
+    }
+
+    #[test]
+    fn words_are_classified_by_kind() {
+        let kind = |text: &str| Word::new(text.to_string()).kind(".!?");
+        assert_eq!(kind("reading"), WordKind::Normal);
+        assert_eq!(kind("42"), WordKind::Number);
+        assert_eq!(kind("$1,000"), WordKind::Number);
+        assert_eq!(kind("12:30"), WordKind::Number);
+        assert_eq!(kind("B2B"), WordKind::Normal);
+        assert_eq!(kind("done."), WordKind::SentenceEnd);
+        assert_eq!(kind("42."), WordKind::SentenceEnd);
+
+        let mut heading = Word::new("Intro.".to_string());
+        heading.heading = true;
+        assert_eq!(heading.kind(".!?"), WordKind::Heading);
+    }
+
+    #[test]
+    fn appending_after_finishing_resumes_from_the_old_end() {
+        let mut engine = engine("hello world");
+        let end = run_until_finished(&mut engine, Instant::now(), Duration::from_millis(10));
+        assert!(engine.is_finished_at(end));
+        assert_eq!(engine.get_progress(), 1.0);
+
+        engine.append("again there");
+        assert!(!engine.is_finished_at(end));
+        assert_eq!(engine.current_index, 2);
+        assert_eq!(engine.get_current_word().map(|w| w.text.as_str()), Some("again"));
+        assert_eq!(engine.get_progress(), 0.5);
+        assert_eq!(engine.current_display_time().map(ms), Some(200));
+
+        let start = Instant::now();
+        engine.reset_dwell_at(start);
+        assert_eq!(engine.update_at(start + Duration::from_millis(210)).map(|w| w.text.as_str()), Some("there"));
+        run_until_finished(&mut engine, start + Duration::from_millis(210), Duration::from_millis(10));
+        assert_eq!(engine.get_progress(), 1.0);
+    }
+
+    #[test]
+    fn appending_mid_read_keeps_the_position() {
+        let mut engine = engine("hello world");
+        engine.append("");
+        assert_eq!(engine.words().len(), 2);
+        engine.append("again there");
+        assert_eq!(engine.current_index, 0);
+        assert_eq!(engine.words().len(), 4);
+    }
+
+    #[test]
+    fn proportional_orp_puts_the_focus_mid_word() {
+        let mut word = Word::new("impossibly".to_string());
+        assert_eq!(word.orp_index, 3);
+        word.set_orp_position(0.5);
+        assert_eq!(word.orp_index, 5);
+        word.set_orp_position(1.0);
+        assert_eq!(word.orp_index, 9);
+        word.set_orp_position(0.0);
+        assert_eq!(word.orp_index, 0);
+    }
+
+    #[test]
+    fn orp_position_applies_to_built_and_appended_words() {
+        let mut engine = RSVPEngine::builder("impossibly").orp_position(Some(0.5)).build();
+        engine.append("remarkable");
+        assert!(engine.words().iter().all(|w| w.orp_index == 5));
+
+        let table = RSVPEngine::builder("impossibly").orp_position(None).build();
+        assert_eq!(table.words()[0].orp_index, 3);
+    }
+
+    #[test]
+    fn emoji_and_currency_tokens_are_symbols_but_words_are_not() {
+        let symbol = |text: &str| Word::new(text.to_string()).is_symbol();
+        assert!(symbol("👍"));
+        assert!(symbol("✅🎉"));
+        assert!(symbol("€"));
+        assert!(symbol("→"));
+        assert!(!symbol("reading"));
+        assert!(!symbol("👍great"));
+        assert!(!symbol("$5"));
+        assert!(!symbol("—"));
+        assert!(!symbol("«"));
+        assert!(!symbol(""));
+    }
+
+    #[test]
+    fn symbols_get_the_fixed_dwell() {
+        let timing = TimingConfig { symbol_dwell_ms: 350, ..TimingConfig::default() };
+        let time = |text: &str, timing: &TimingConfig| word_display_time(&Word::new(text.to_string()), 300, timing);
+        assert_eq!(ms(time("👍", &timing)), 350);
+        assert_eq!(ms(time("€", &timing)), 350);
+        assert_eq!(ms(time("hello", &timing)), 200);
+        assert_eq!(ms(time("👍", &TimingConfig::default())), ms(time("a", &TimingConfig::default())));
+    }
+
+    #[test]
+    fn no_warmup_starts_at_the_target_speed() {
+        let mut engine = RSVPEngine::builder("hello world again").start_wpm(150).target_wpm(600).warmup_words(0).build();
+        let start = Instant::now();
+        engine.reset_dwell_at(start);
+        assert_eq!(engine.update_at(start).map(|w| w.text.as_str()), Some("hello"));
+        assert_eq!(engine.get_current_wpm(), 600);
+        assert_eq!(engine.update_at(start + Duration::from_millis(110)).map(|w| w.text.as_str()), Some("world"));
+        assert_eq!(engine.get_current_wpm(), 600);
+    }
+
+    #[test]
+    fn zero_speed_never_gives_an_endless_dwell() {
+        let time = word_display_time(&Word::new("hello".to_string()), 0, &TimingConfig::default());
+        assert!(time.as_secs_f32().is_finite());
+        assert_eq!(time, word_display_time(&Word::new("hello".to_string()), 1, &TimingConfig::default()));
+    }
+
+    #[test]
+    fn sentence_ends_and_commas_get_their_multipliers() {
+        // Syllable timing, so the punctuation mark adds no length of its own
+        let timing = TimingConfig { mode: TimingMode::Syllables, ..TimingConfig::default() };
+        let time = |text: &str| word_display_time(&Word::new(text.to_string()), 300, &timing).as_secs_f32();
+        assert!((time("hello.") / time("hello") - 1.4).abs() < 1e-4);
+        assert!((time("hello,") / time("hello") - 1.15).abs() < 1e-4);
+    }
+
+    #[test]
+    fn engine_and_shared_timing_agree() {
+        let text = "Short words, then a sentence end. Internationalization!";
+        let timing = TimingConfig::default();
+        let mut engine = RSVPEngine::builder(text).start_wpm(300).target_wpm(300).warmup_words(0).timing(timing.clone()).build();
+        for index in 0..engine.words().len() {
+            engine.seek_to(index);
+            let word = &engine.words()[index];
+            assert_eq!(engine.current_display_time(), Some(word_display_time(word, 300, &timing)), "{}", word.text);
+        }
+    }
+
+    #[test]
+    fn word_before_a_blank_line_gets_the_paragraph_pause() {
+        let mut engine = engine("hello world there\n\nagain hello");
+        let times: Vec<u64> = (0..engine.words().len())
+            .map(|index| {
+                engine.seek_to(index);
+                engine.current_display_time().map(ms).unwrap()
+            })
+            .collect();
+        assert_eq!(times, [200, 200, 400, 200, 200]);
+        assert_eq!(engine.words()[2].break_after, BreakKind::Paragraph);
+
+        let timing = TimingConfig { paragraph_pause_factor: 3.0, ..TimingConfig::default() };
+        let engine = RSVPEngine::builder("there\n\nagain").timing(timing).build();
+        assert_eq!(engine.current_display_time().map(ms), Some(600));
+    }
+
+    #[test]
+    fn display_limits_hold_fast_and_slow_words() {
+        let limits = (Duration::from_millis(60), Duration::from_millis(2000));
+        let fast = RSVPEngine::builder("to").start_wpm(1200).target_wpm(1200).warmup_words(0).display_limits(limits.0, limits.1).build();
+        assert_eq!(fast.current_display_time(), Some(limits.0));
+
+        // Near 900 ms unclamped at 100 wpm; a tighter ceiling holds it
+        let slow = RSVPEngine::builder("Incomprehensibilities")
+            .start_wpm(100)
+            .target_wpm(100)
+            .warmup_words(0)
+            .max_word_chars(40)
+            .display_limits(limits.0, Duration::from_millis(800))
+            .build();
+        assert_eq!(slow.current_display_time(), Some(Duration::from_millis(800)));
+
+        let unclamped = word_display_time(&Word::new("to".to_string()), 1200, &TimingConfig::default());
+        assert!(unclamped < limits.0);
+    }
+
+    #[test]
+    fn a_long_frame_gap_is_made_up_over_the_next_frames() {
+        // "hello" takes 100 ms at 600 wpm, so a 500 ms gap owes five words
+        let mut engine = RSVPEngine::builder(&["hello"; 10].join(" ")).start_wpm(600).target_wpm(600).warmup_words(0).build();
+        let start = Instant::now();
+        engine.reset_dwell_at(start);
+        engine.update_at(start + Duration::from_millis(510));
+        assert_eq!(engine.current_index, 1);
+
+        // One word per frame until the schedule is met, so each of them is shown
+        let mut indices = Vec::new();
+        for frame in 1..=5 {
+            engine.update_at(start + Duration::from_millis(510 + 16 * frame));
+            indices.push(engine.current_index);
+        }
+        assert_eq!(indices, [2, 3, 4, 5, 5]);
+        assert_eq!(engine.words_read(), 5);
+
+        // Nothing was lost: the next word is due when it would have been without the gap
+        engine.update_at(start + Duration::from_millis(595));
+        assert_eq!(engine.current_index, 5);
+        engine.update_at(start + Duration::from_millis(605));
+        assert_eq!(engine.current_index, 6);
+    }
+
+    #[test]
+    fn a_late_frame_returns_the_next_word() {
+        let mut engine = engine("hello world again there");
+        let start = Instant::now();
+        engine.reset_dwell_at(start);
+        let word = engine.update_at(start + Duration::from_millis(610)).map(|w| w.text.clone());
+        assert_eq!(word.as_deref(), Some("world"));
+        assert_eq!(engine.current_index, 1);
+    }
+
+    #[test]
+    fn resume_rewinds_the_set_number_of_words() {
+        let rewinding = |text: &str| RSVPEngine::builder(text).rewind_on_resume(2).build();
+        let mut rewound = rewinding("one two three four five six");
+        rewound.seek_to(4);
+        rewound.pause();
+        rewound.resume();
+        assert_eq!(rewound.current_index, 2);
+
+        // Resuming an engine that wasn't paused doesn't step back
+        rewound.resume();
+        assert_eq!(rewound.current_index, 2);
+
+        let mut near_start = rewinding("one two three");
+        near_start.seek_to(1);
+        near_start.pause();
+        near_start.resume();
+        assert_eq!(near_start.current_index, 0);
+
+        let mut stock = engine("one two three four");
+        stock.seek_to(3);
+        stock.pause();
+        stock.resume();
+        assert_eq!(stock.current_index, 3);
+    }
+
+    #[test]
+    fn unit_punctuation_factors_remove_the_slowdown() {
+        let timing = TimingConfig {
+            mode: TimingMode::Syllables,
+            sentence_pause_factor: 1.0,
+            comma_pause_factor: 1.0,
+            ..TimingConfig::default()
+        };
+        let time = |text: &str| word_display_time(&Word::new(text.to_string()), 300, &timing);
+        assert_eq!(time("hello."), time("hello"));
+        assert_eq!(time("hello,"), time("hello"));
+        assert_eq!(time("hello?"), time("hello"));
+    }
+
+    #[test]
+    fn negative_pause_factors_never_panic() {
+        let timing = TimingConfig { sentence_pause_factor: -1.0, comma_pause_factor: f32::NAN, ..TimingConfig::default() };
+        let time = |text: &str| word_display_time(&Word::new(text.to_string()), 300, &timing);
+        assert_eq!(time("hello."), Duration::ZERO);
+        // A NaN product stops at the cap
+        assert!(time("hello,") <= time("hello").mul_f32(timing.max_pause_factor));
+    }
+
+    #[test]
+    fn sentence_seeks_land_on_sentence_starts() {
+        // Sentences start at 0, 3, 5 and 8
+        let mut engine = engine("One two three. Four five! Six seven eight? Nine ten");
+        let landings: Vec<usize> = (0..4)
+            .map(|_| {
+                engine.seek_sentence(1);
+                engine.current_index
+            })
+            .collect();
+        assert_eq!(landings, [3, 5, 8, 8]);
+
+        engine.seek_to(6);
+        engine.seek_sentence(-1);
+        assert_eq!(engine.current_index, 5);
+        engine.seek_sentence(-1);
+        assert_eq!(engine.current_index, 3);
+        engine.seek_sentence(-1);
+        engine.seek_sentence(-1);
+        assert_eq!(engine.current_index, 0);
+    }
+
+    /// Strategy that records every word it is asked to time, at one word per beat
+    struct RecordingTiming(std::rc::Rc<std::cell::RefCell<Vec<String>>>);
+
+    impl TimingStrategy for RecordingTiming {
+        fn duration(&self, word: &str, wpm: u32) -> Duration {
+            self.0.borrow_mut().push(word.to_string());
+            Duration::from_secs_f32(60.0 / wpm as f32)
+        }
+    }
+
+    fn recording_engine(text: &str, warmup_words: u32) -> (RSVPEngine, std::rc::Rc<std::cell::RefCell<Vec<String>>>) {
+        let calls = std::rc::Rc::default();
+        let engine = RSVPEngine::builder(text)
+            .start_wpm(150)
+            .target_wpm(300)
+            .warmup_words(warmup_words)
+            .timing_strategy(Box::new(RecordingTiming(std::rc::Rc::clone(&calls))))
+            .build();
+        (engine, calls)
+    }
+
+    #[test]
+    fn steady_speed_reads_precomputed_durations() {
+        let (mut engine, calls) = recording_engine("one two three four five six", 0);
+        assert_eq!(calls.borrow().len(), 6); // Each word timed once, up front
+        calls.borrow_mut().clear();
+
+        let start = Instant::now();
+        engine.reset_dwell_at(start);
+        for frame in 1..=210 {
+            engine.update_at(start + Duration::from_millis(5 * frame));
+        }
+        assert_eq!(engine.current_index, 5);
+        assert!(calls.borrow().is_empty());
+
+        engine.adjust_speed(100);
+        assert_eq!(calls.borrow().len(), 6);
+    }
+
+    #[test]
+    fn swapped_in_strategy_times_every_word() {
+        // Called once per word when the durations are worked out
+        let (_, calls) = recording_engine("one two three four", 0);
+        assert_eq!(*calls.borrow(), ["one", "two", "three", "four"]);
+
+        // During the warmup each word is timed at its own ramped speed
+        let (mut engine, calls) = recording_engine("one two three four", 10);
+        calls.borrow_mut().clear();
+        let end = run_until_finished(&mut engine, Instant::now(), Duration::from_millis(5));
+
+        let mut timed = calls.borrow().clone();
+        timed.dedup();
+        assert_eq!(timed, ["one", "two", "three", "four"]);
+        assert!(engine.is_finished_at(end));
+        assert_eq!(engine.words_read(), 4);
+    }
+
+    #[test]
+    fn smooth_change_eases_toward_the_new_target() {
+        let text = ["hello"; 30].join(" ");
+        let mut engine = RSVPEngine::builder(&text).start_wpm(300).target_wpm(300).warmup_words(0).smooth_changes(true).build();
+        engine.adjust_speed(300);
+        assert_eq!(engine.get_target_wpm(), 600);
+        assert_eq!(engine.get_current_wpm(), 300);
+
+        let start = Instant::now();
+        engine.reset_dwell_at(start);
+        let mut now = start;
+        let mut speeds = Vec::new();
+        while engine.words_read() <= RSVPEngine::RAMP_WORDS + 2 {
+            now += Duration::from_millis(5);
+            let before = engine.words_read();
+            engine.update_at(now);
+            if engine.words_read() > before {
+                speeds.push(engine.get_current_wpm());
+            }
+        }
+        assert!(speeds.windows(2).all(|pair| pair[1] >= pair[0]));
+        assert!(speeds[0] < 600);
+        assert_eq!(speeds.last(), Some(&600));
+    }
+
+    #[test]
+    fn instant_change_is_still_the_default() {
+        let mut engine = engine("hello world");
+        engine.adjust_speed(300);
+        assert_eq!(engine.get_current_wpm(), 600);
+    }
+}
@@ -0,0 +1,152 @@
+use crate::config::Config;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Words read per day and reading totals, persisted as `stats.json` in the app directory
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReadingStats {
+    days: BTreeMap<String, u64>, // Local date ("2024-03-09") -> words read that day
+    total_seconds: f64,          // Time spent with the reader open
+    sessions: u64,               // Reads started
+}
+
+impl ReadingStats {
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    /// Write to a temporary file and rename it into place, so a crash mid-save
+    /// leaves the previous stats intact rather than a truncated file
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        let temp = path.with_extension("json.tmp");
+        fs::write(&temp, serde_json::to_string_pretty(self)?)?;
+        fs::rename(temp, path)?;
+        Ok(())
+    }
+
+    fn path() -> Result<PathBuf> {
+        Ok(Config::app_dir()?.join("stats.json"))
+    }
+
+    /// Count `words` toward `day`. Each day has its own tally, so a new day starts from zero.
+    pub fn add_words(&mut self, day: &str, words: u64) {
+        if words > 0 {
+            *self.days.entry(day.to_string()).or_insert(0) += words;
+        }
+    }
+
+    pub fn words_on(&self, day: &str) -> u64 {
+        self.days.get(day).copied().unwrap_or(0)
+    }
+
+    /// Count one more read
+    pub fn start_session(&mut self) {
+        self.sessions += 1;
+    }
+
+    pub fn add_reading_time(&mut self, time: Duration) {
+        self.total_seconds += time.as_secs_f64();
+    }
+
+    /// Words read over all days
+    pub fn total_words(&self) -> u64 {
+        self.days.values().sum()
+    }
+
+    pub fn total_time(&self) -> Duration {
+        Duration::from_secs_f64(self.total_seconds.max(0.0))
+    }
+
+    pub fn sessions(&self) -> u64 {
+        self.sessions
+    }
+}
+
+/// Today's local date, `YYYY-MM-DD`
+pub fn today() -> String {
+    // SAFETY: `time` accepts a null pointer and `localtime_*` only write to `tm`
+    let tm = unsafe {
+        let now = libc::time(std::ptr::null_mut());
+        let mut tm: libc::tm = std::mem::zeroed();
+        #[cfg(not(windows))]
+        if libc::localtime_r(&now, &mut tm).is_null() {
+            libc::gmtime_r(&now, &mut tm);
+        }
+        // The CRT has the `_s` forms instead, arguments swapped and 0 on success
+        #[cfg(windows)]
+        if libc::localtime_s(&mut tm, &now) != 0 {
+            libc::gmtime_s(&mut tm, &now);
+        }
+        tm
+    };
+    format!("{:04}-{:02}-{:02}", tm.tm_year + 1900, tm.tm_mon + 1, tm.tm_mday)
+}
+
+/// Progress line such as "3,200 / 10,000 words today"
+pub fn goal_text(words_today: u64, goal: u64) -> String {
+    format!("{} / {} words today", with_separators(words_today), with_separators(goal))
+}
+
+/// Menu line such as "Read 12,340 words"
+pub fn total_text(total_words: u64) -> String {
+    format!("Read {} words", with_separators(total_words))
+}
+
+fn with_separators(n: u64) -> String {
+    let digits: Vec<char> = n.to_string().chars().collect();
+    let groups: Vec<String> = digits.rchunks(3).rev().map(|group| group.iter().collect()).collect();
+    groups.join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn words_accumulate_within_a_day() {
+        let mut stats = ReadingStats::default();
+        stats.add_words("2024-03-09", 1200);
+        stats.add_words("2024-03-09", 2000);
+        stats.add_words("2024-03-09", 0);
+        assert_eq!(stats.words_on("2024-03-09"), 3200);
+        assert_eq!(stats.total_words(), 3200);
+    }
+
+    #[test]
+    fn a_new_day_starts_from_zero() {
+        let mut stats = ReadingStats::default();
+        stats.add_words("2024-03-09", 3200);
+        assert_eq!(stats.words_on("2024-03-10"), 0);
+        stats.add_words("2024-03-10", 150);
+        assert_eq!(stats.words_on("2024-03-10"), 150);
+        assert_eq!(stats.words_on("2024-03-09"), 3200);
+        assert_eq!(stats.total_words(), 3350);
+    }
+
+    #[test]
+    fn per_day_counts_survive_a_round_trip() {
+        let mut stats = ReadingStats::default();
+        stats.add_words("2024-03-09", 42);
+        stats.start_session();
+        let loaded: ReadingStats = serde_json::from_str(&serde_json::to_string(&stats).unwrap()).unwrap();
+        assert_eq!(loaded.words_on("2024-03-09"), 42);
+        assert_eq!(loaded.sessions(), 1);
+    }
+
+    #[test]
+    fn goal_text_groups_thousands() {
+        assert_eq!(goal_text(3200, 10_000), "3,200 / 10,000 words today");
+        assert_eq!(goal_text(0, 500), "0 / 500 words today");
+        assert_eq!(total_text(1_234_567), "Read 1,234,567 words");
+    }
+}
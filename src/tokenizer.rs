@@ -0,0 +1,339 @@
+use crate::language::Script;
+use crate::rsvp_engine::{BreakKind, Word};
+
+/// How text is split into displayed units
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tokenization {
+    Words,      // Whitespace-separated words
+    Characters, // One unit per CJK character, other runs kept whole
+}
+
+/// Split `text` into words, recording whether a line or paragraph break follows each one.
+/// Runs of delimiters never produce empty words, and tokens longer than `max_word_chars`
+/// are cut into readable segments.
+pub fn tokenize(text: &str, tokenization: Tokenization, max_word_chars: usize) -> Vec<Word> {
+    let mut words: Vec<Word> = Vec::new();
+    let mut run = String::new();
+    let mut gap_newlines = 0;
+    let mut after_cjk = false; // Last unit was a lone CJK character with nothing in between
+
+    for c in text.chars() {
+        if c.is_whitespace() {
+            flush(&mut run, &mut words, max_word_chars);
+            after_cjk = false;
+            if c == '\n' {
+                gap_newlines += 1;
+                if let Some(last) = words.last_mut() {
+                    last.break_after = if gap_newlines >= 2 {
+                        BreakKind::Paragraph
+                    } else {
+                        BreakKind::Line
+                    };
+                }
+            }
+            continue;
+        }
+        gap_newlines = 0;
+
+        if tokenization == Tokenization::Characters {
+            if Script::of_char(c) == Some(Script::Cjk) {
+                flush(&mut run, &mut words, max_word_chars);
+                words.push(Word::new(c.to_string()));
+                after_cjk = true;
+                continue;
+            }
+            if after_cjk && !c.is_alphanumeric() {
+                // Punctuation trailing a character stays with it so pauses still apply
+                if let Some(last) = words.last_mut() {
+                    last.text.push(c);
+                }
+                continue;
+            }
+            after_cjk = false;
+        }
+
+        run.push(c);
+    }
+    flush(&mut run, &mut words, max_word_chars);
+
+    words
+}
+
+fn flush(run: &mut String, words: &mut Vec<Word>, max_word_chars: usize) {
+    if run.is_empty() {
+        return;
+    }
+    let run = std::mem::take(run);
+    let chars: Vec<char> = run.chars().collect();
+    let max = max_word_chars.max(1);
+    if chars.len() <= max {
+        words.push(Word::new(run));
+        return;
+    }
+
+    // Over-long tokens (URLs, paths, hashes) are shown in pieces of at most `max`
+    let mut start = 0;
+    while start < chars.len() {
+        let rest = chars.len() - start;
+        let end = if rest <= max {
+            chars.len()
+        } else {
+            // Cut at the last natural break that fits: after a '/', '-' or '_', or before
+            // a '.' so a piece never ends like a sentence. Without one, cut into even pieces.
+            (start + 1..=start + max)
+                .rev()
+                .find(|&i| matches!(chars[i - 1], '/' | '-' | '_') || chars[i] == '.')
+                .unwrap_or_else(|| start + rest.div_ceil(rest.div_ceil(max)))
+        };
+        words.push(Word::new(chars[start..end].iter().collect()));
+        start = end;
+    }
+}
+
+/// Flag the words of lines written entirely in capitals as headings. Capitalized
+/// words inside ordinary prose, like acronyms, share a line with lowercase words
+/// and are left alone.
+pub fn mark_headings(words: &mut [Word]) {
+    let mut line_start = 0;
+    for i in 0..words.len() {
+        if words[i].break_after == BreakKind::None && i + 1 < words.len() {
+            continue;
+        }
+
+        let line = &mut words[line_start..=i];
+        let has_capitals = line.iter().any(|w| w.text.chars().any(char::is_uppercase));
+        let has_lowercase = line.iter().any(|w| w.text.chars().any(char::is_lowercase));
+        let heading = has_capitals && !has_lowercase;
+        for word in line {
+            word.heading = heading;
+        }
+        line_start = i + 1;
+    }
+}
+
+/// Shortest run of spaced-out letters taken for an emphasized word; shorter runs
+/// are more likely real one-letter words ("a", "I")
+const MIN_SPACED_LETTERS: usize = 3;
+
+/// The letter of a one-letter token, allowing trailing punctuation ("T,")
+fn spaced_letter(word: &Word) -> Option<char> {
+    let core = word.text.trim_end_matches(|c: char| !c.is_alphanumeric());
+    let mut chars = core.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_alphabetic() => Some(c),
+        _ => None,
+    }
+}
+
+/// Join runs of single letters on one line, like "N O T", back into the word they
+/// spell, flagged as emphasis. Letters must share a case, and only the last may carry
+/// punctuation or a line break.
+pub fn merge_spaced_emphasis(words: Vec<Word>) -> Vec<Word> {
+    let mut merged: Vec<Word> = Vec::with_capacity(words.len());
+    let mut i = 0;
+    while i < words.len() {
+        let mut end = i;
+        if let Some(first) = spaced_letter(&words[i]) {
+            while end + 1 < words.len() {
+                let word = &words[end];
+                let continues = word.break_after == BreakKind::None && word.text.chars().count() == 1;
+                let next_matches = spaced_letter(&words[end + 1])
+                    .is_some_and(|c| c.is_uppercase() == first.is_uppercase());
+                if !(continues && next_matches) {
+                    break;
+                }
+                end += 1;
+            }
+        }
+
+        if end + 1 - i < MIN_SPACED_LETTERS {
+            merged.push(words[i].clone());
+            i += 1;
+            continue;
+        }
+
+        let run = &words[i..=end];
+        let mut word = Word::new(run.iter().map(|w| w.text.as_str()).collect());
+        word.break_after = run[run.len() - 1].break_after;
+        word.emphasis = true;
+        merged.push(word);
+        i = end + 1;
+    }
+    merged
+}
+
+/// Index in `words` where `run` appears word for word, e.g. to find the text that
+/// was being read inside a longer copy of it. None for an empty `run`.
+pub fn locate_run(words: &[Word], run: &[Word]) -> Option<usize> {
+    if run.is_empty() {
+        return None;
+    }
+    words
+        .windows(run.len())
+        .position(|window| window.iter().zip(run).all(|(a, b)| a.text == b.text))
+}
+
+/// Group consecutive words into chunks of up to `size`, flashed together as one word
+/// joined by `joiner`. A chunk ends early at a sentence end or a line break, so those
+/// pauses still fall between chunks. Each chunk keeps its words as `parts` for timing.
+pub fn chunk_words(words: Vec<Word>, size: usize, terminators: &str, joiner: &str) -> Vec<Word> {
+    let mut chunks = Vec::new();
+    let mut pending: Vec<Word> = Vec::new();
+
+    for word in words {
+        let closes = word.break_after != BreakKind::None || word.is_sentence_end(terminators);
+        pending.push(word);
+        if closes || pending.len() >= size {
+            chunks.push(join_chunk(std::mem::take(&mut pending), joiner));
+        }
+    }
+    if !pending.is_empty() {
+        chunks.push(join_chunk(pending, joiner));
+    }
+    chunks
+}
+
+fn join_chunk(mut parts: Vec<Word>, joiner: &str) -> Word {
+    if parts.len() == 1 {
+        return parts.remove(0);
+    }
+    let text = parts.iter().map(|w| w.text.as_str()).collect::<Vec<_>>().join(joiner);
+    let mut chunk = Word::new(text);
+    chunk.break_after = parts[parts.len() - 1].break_after;
+    chunk.heading = parts.iter().all(|w| w.heading);
+    chunk.emphasis = parts.iter().any(|w| w.emphasis);
+    chunk.parts = parts;
+    chunk
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TimingConfig;
+    use crate::rsvp_engine::{word_display_time, RSVPEngine};
+
+    fn texts(words: &[Word]) -> Vec<&str> {
+        words.iter().map(|w| w.text.as_str()).collect()
+    }
+
+    #[test]
+    fn doubled_delimiters_give_no_empty_words() {
+        let words = tokenize("  one,  two \t\t three\n\n\n four  ", Tokenization::Words, 20);
+        assert_eq!(texts(&words), ["one,", "two", "three", "four"]);
+        assert!(words.iter().all(|w| w.get_parts().is_some()));
+        assert!(tokenize(" \n\t ", Tokenization::Words, 20).is_empty());
+    }
+
+    #[test]
+    fn empty_word_has_nothing_to_render() {
+        let word = Word::new(String::new());
+        assert_eq!(word.get_parts(), None);
+        assert_eq!(word.orp_char(), None);
+    }
+
+    #[test]
+    fn all_caps_line_is_a_heading_but_inline_acronyms_are_not() {
+        let mut words = tokenize("CHAPTER ONE\nThe NASA probe and the FBI agent met.", Tokenization::Words, 20);
+        mark_headings(&mut words);
+        let headings: Vec<&str> = words.iter().filter(|w| w.heading).map(|w| w.text.as_str()).collect();
+        assert_eq!(headings, ["CHAPTER", "ONE"]);
+    }
+
+    #[test]
+    fn heading_words_dwell_longer() {
+        let timing = TimingConfig { detect_headings: true, ..Default::default() };
+        let engine = RSVPEngine::builder("TITLE\nwords").timing(timing.clone()).build();
+        let [title, body] = engine.words() else { panic!("two words expected") };
+        assert!(title.heading && !body.heading);
+        let time = |word| word_display_time(word, 300, &timing).as_millis();
+        // Same length; the heading factor is the only difference
+        assert_eq!(time(title), time(body) * 3 / 2);
+    }
+
+    #[test]
+    fn long_blob_is_cut_into_readable_pieces() {
+        let blob: String = "QmFzZTY0IGJsb2IgZGF0YQ".chars().cycle().take(5000).collect();
+        let words = tokenize(&blob, Tokenization::Words, 20);
+        assert_eq!(words.len(), 250);
+        assert!(words.iter().all(|w| (1..=20).contains(&w.text.chars().count())));
+        assert_eq!(words.iter().map(|w| w.text.as_str()).collect::<String>(), blob);
+    }
+
+    #[test]
+    fn long_url_is_cut_at_natural_breaks() {
+        let words = tokenize("https://example.com/some-long/path_name", Tokenization::Words, 20);
+        assert_eq!(texts(&words), ["https://example.com/", "some-long/path_name"]);
+    }
+
+    #[test]
+    fn pieces_count_toward_progress() {
+        let blob = "x".repeat(5000);
+        let engine = RSVPEngine::builder(&blob).max_word_chars(20).build();
+        assert_eq!(engine.total_words(), 250);
+        assert_eq!(engine.position(), 1);
+    }
+
+    #[test]
+    fn spaced_letters_merge_into_an_emphasized_word() {
+        let words = merge_spaced_emphasis(tokenize("she was N O T happy", Tokenization::Words, 20));
+        assert_eq!(texts(&words), ["she", "was", "NOT", "happy"]);
+        assert!(words[2].emphasis);
+        assert!(words.iter().filter(|w| w.emphasis).count() == 1);
+    }
+
+    #[test]
+    fn short_or_mixed_letter_runs_stay_apart() {
+        let words = merge_spaced_emphasis(tokenize("a I saw it, b C d", Tokenization::Words, 20));
+        assert_eq!(texts(&words), ["a", "I", "saw", "it,", "b", "C", "d"]);
+        let words = merge_spaced_emphasis(tokenize("W O W, ok", Tokenization::Words, 20));
+        assert_eq!(texts(&words), ["WOW,", "ok"]);
+    }
+
+    #[test]
+    fn emphasized_words_dwell_longer() {
+        let timing = TimingConfig { detect_spaced_emphasis: true, ..TimingConfig::default() };
+        let engine = RSVPEngine::builder("was N O T happy").timing(timing.clone()).build();
+        let merged = &engine.words()[1];
+        assert_eq!(merged.text, "NOT");
+        let plain = Word::new("NOT".to_string());
+        let time = |word| word_display_time(word, 300, &timing);
+        assert_eq!(time(merged), time(&plain).mul_f32(timing.emphasis_factor));
+    }
+
+    #[test]
+    fn prepended_paragraph_shifts_the_resumed_index() {
+        let article = "The quick brown fox jumps over the lazy dog.";
+        let longer = format!("A short intro of six words.\n\n{}", article);
+        let old = tokenize(article, Tokenization::Words, 20);
+        let new = tokenize(&longer, Tokenization::Words, 20);
+        let offset = locate_run(&new, &old).unwrap();
+        assert_eq!(offset, 6);
+        let resumed = offset + 4;
+        assert_eq!(new[resumed].text, old[4].text);
+    }
+
+    #[test]
+    fn unrelated_or_empty_runs_are_not_located() {
+        let words = tokenize("one two three", Tokenization::Words, 20);
+        assert_eq!(locate_run(&words, &tokenize("two four", Tokenization::Words, 20)), None);
+        assert_eq!(locate_run(&words, &[]), None);
+        assert_eq!(locate_run(&words, &tokenize("one two three four", Tokenization::Words, 20)), None);
+    }
+
+    #[test]
+    fn url_pieces_are_timed_and_progress_one_by_one() {
+        let url = "https://example.com/articles/2024/a-rather-long-slug_with_parts";
+        let mut engine = RSVPEngine::builder(url).max_word_chars(20).build();
+        let pieces = engine.words().len();
+        assert!(pieces > 2);
+        assert!(engine.words().iter().all(|w| w.text.chars().count() <= 20));
+
+        let mut progress = Vec::new();
+        for index in 0..pieces {
+            engine.seek_to(index);
+            assert!(engine.current_display_time().is_some_and(|time| !time.is_zero()));
+            progress.push(engine.get_progress());
+        }
+        assert!(progress.windows(2).all(|pair| pair[1] > pair[0]));
+    }
+}